@@ -25,10 +25,9 @@ bindgen \
  --generate functions,types \
  crates/glfw3-sys/bindgen/platform/cocoa.h
 
-# X11
+# X11 (GLFW loads libX11 at runtime, so there is nothing to link)
 bindgen \
  --raw-line 'use crate::*;' \
- --raw-line 'mod link;' \
  --allowlist-function "glfwGetX11.*" \
  --allowlist-function "glfwSetX11.*" \
  --allowlist-function "glfwGetGLX.*" \
@@ -39,10 +38,9 @@ bindgen \
  --generate functions,types \
  crates/glfw3-sys/bindgen/platform/x11.h
 
-# Wayland
+# Wayland (GLFW loads libwayland-client at runtime, so there is nothing to link)
 bindgen \
  --raw-line 'use crate::*;' \
- --raw-line 'mod link;' \
  --raw-line 'pub type wl_output = core::ffi::c_void;' \
  --raw-line 'pub type wl_display = core::ffi::c_void;' \
  --raw-line 'pub type wl_surface = core::ffi::c_void;' \
@@ -57,6 +55,17 @@ bindgen \
  --generate functions,types \
  crates/glfw3-sys/bindgen/platform/wayland.h
 
+# OSMesa
+bindgen \
+ --raw-line 'use crate::*;' \
+ --allowlist-function "glfwGetOSMesa.*" \
+ --blocklist-type GLFWwindow \
+ --blocklist-type GLFWmonitor \
+ --ctypes-prefix "core::ffi" \
+ -o crates/glfw3-sys/src/ffi/platform/osmesa/mod.rs \
+ --generate functions,types \
+ crates/glfw3-sys/bindgen/platform/osmesa.h
+
  # Functions and Types
 bindgen \
  --raw-line 'mod link;' \
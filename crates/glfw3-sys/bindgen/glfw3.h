@@ -1,3 +1,14 @@
+typedef struct VkInstance_T* VkInstance;
+typedef struct VkPhysicalDevice_T* VkPhysicalDevice;
+typedef unsigned long long VkSurfaceKHR;
+typedef struct VkAllocationCallbacks VkAllocationCallbacks;
+typedef int VkResult;
+typedef void (*PFN_vkVoidFunction)(void);
+typedef PFN_vkVoidFunction (*PFN_vkGetInstanceProcAddr)(VkInstance instance, const char* pName);
+
+// Declares the Vulkan functions without requiring the Vulkan headers
+#define VK_VERSION_1_0 1
+
 #define GLFW_INCLUDE_NONE
 #define GLFW_NATIVE_INCLUDE_NONE
 
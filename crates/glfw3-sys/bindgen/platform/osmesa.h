@@ -0,0 +1,8 @@
+typedef void* OSMesaContext;
+
+#define GLFW_INCLUDE_NONE
+#define GLFW_NATIVE_INCLUDE_NONE
+#define GLFW_EXPOSE_NATIVE_OSMESA
+
+#include "../../vendor/glfw/include/GLFW/glfw3.h"
+#include "../../vendor/glfw/include/GLFW/glfw3native.h"
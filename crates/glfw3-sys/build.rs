@@ -6,13 +6,13 @@ fn main() {
     cfg.define("GLFW_BUILD_EXAMPLES", "OFF")
         .define("GLFW_BUILD_TESTS", "OFF")
         .define("GLFW_BUILD_DOCS", "OFF")
-        .define("CMAKE_INSTALL_LIBDIR", "lib");
+        .define("CMAKE_INSTALL_LIBDIR", "lib")
+        // Build both backends so that the platform can be selected at runtime with
+        // `GLFW_PLATFORM`. GLFW ignores these options outside of Linux and the BSDs.
+        .define("GLFW_BUILD_X11", "ON")
+        .define("GLFW_BUILD_WAYLAND", "ON");
 
-    let dst = if cfg!(feature = "wayland") {
-        cfg.define("GLFW_USE_WAYLAND", "ON").build()
-    } else {
-        cfg.define("GLFW_USE_WAYLAND", "OFF").build()
-    };
+    let dst = cfg.build();
 
     println!(
         "cargo:rustc-link-search=native={}",
@@ -2,6 +2,33 @@
 
 mod link;
 
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkInstance_T {
+    _unused: [u8; 0],
+}
+pub type VkInstance = *mut VkInstance_T;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkPhysicalDevice_T {
+    _unused: [u8; 0],
+}
+pub type VkPhysicalDevice = *mut VkPhysicalDevice_T;
+pub type VkSurfaceKHR = core::ffi::c_ulonglong;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkAllocationCallbacks {
+    _unused: [u8; 0],
+}
+pub type VkResult = core::ffi::c_int;
+pub type PFN_vkVoidFunction = ::std::option::Option<unsafe extern "C" fn()>;
+pub type PFN_vkGetInstanceProcAddr = ::std::option::Option<
+    unsafe extern "C" fn(
+        instance: VkInstance,
+        pName: *const core::ffi::c_char,
+    ) -> PFN_vkVoidFunction,
+>;
+
 #[doc = " @brief Client API function pointer type.\n\n  Generic function pointer used for returning client API function pointers\n  without forcing a cast from a regular pointer.\n\n  @sa @ref context_glext\n  @sa @ref glfwGetProcAddress\n\n  @since Added in version 3.0.\n\n  @ingroup context"]
 pub type GLFWglproc = ::std::option::Option<unsafe extern "C" fn()>;
 #[doc = " @brief Vulkan API function pointer type.\n\n  Generic function pointer used for returning Vulkan API function pointers\n  without forcing a cast from a regular pointer.\n\n  @sa @ref vulkan_proc\n  @sa @ref glfwGetInstanceProcAddress\n\n  @since Added in version 3.2.\n\n  @ingroup vulkan"]
@@ -268,6 +295,10 @@ unsafe extern "C" {
     #[doc = " @brief Sets the init allocator to the desired value.\n\n  To use the default allocator, call this function with a `NULL` argument.\n\n  If you specify an allocator struct, every member must be a valid function\n  pointer.  If any member is `NULL`, this function will emit @ref\n  GLFW_INVALID_VALUE and the init allocator will be unchanged.\n\n  The functions in the allocator must fulfil a number of requirements.  See the\n  documentation for @ref GLFWallocatefun, @ref GLFWreallocatefun and @ref\n  GLFWdeallocatefun for details.\n\n  @param[in] allocator The allocator to use at the next initialization, or\n  `NULL` to use the default one.\n\n  @errors Possible errors include @ref GLFW_INVALID_VALUE.\n\n  @pointer_lifetime The specified allocator is copied before this function\n  returns.\n\n  @thread_safety This function must only be called from the main thread.\n\n  @sa @ref init_allocator\n  @sa @ref glfwInit\n\n  @since Added in version 3.4.\n\n  @ingroup init"]
     pub fn glfwInitAllocator(allocator: *const GLFWallocator);
 }
+unsafe extern "C" {
+    #[doc = " @brief Sets the desired Vulkan `vkGetInstanceProcAddr` function.\n\n  This function sets the `vkGetInstanceProcAddr` function that GLFW will use for all\n  Vulkan related entry point queries.\n\n  This feature is mostly useful on macOS, if your copy of the Vulkan loader is in\n  a location where GLFW cannot find it through dynamic loading, or if you are still\n  using the static library version of the loader.\n\n  If set to `NULL`, GLFW will try to load the Vulkan loader dynamically by its standard\n  name and get this function from there.  This is the default behavior.\n\n  The standard name of the loader is `vulkan-1.dll` on Windows, `libvulkan.so.1` on\n  Linux and other Unix-like systems and `libvulkan.1.dylib` on macOS.  If your code is\n  also loading it via these names then you probably don't need to use this function.\n\n  The function address you set is never reset by GLFW, but it only takes effect during\n  initialization.  Once GLFW has been initialized, any updates will be ignored until the\n  library is terminated and initialized again.\n\n  @param[in] loader The address of the function to use, or `NULL`.\n\n  @par Loader function signature\n  @code\n  PFN_vkVoidFunction vkGetInstanceProcAddr(VkInstance instance, const char* name)\n  @endcode\n  For more information about this function, see the\n  [Vulkan Registry](https://www.khronos.org/registry/vulkan/).\n\n  @errors None.\n\n  @remark This function may be called before @ref glfwInit.\n\n  @thread_safety This function must only be called from the main thread.\n\n  @sa @ref vulkan_loader\n  @sa @ref glfwInit\n\n  @since Added in version 3.4.\n\n  @ingroup init"]
+    pub fn glfwInitVulkanLoader(loader: PFN_vkGetInstanceProcAddr);
+}
 unsafe extern "C" {
     #[doc = " @brief Retrieves the version of the GLFW library.\n\n  This function retrieves the major, minor and revision numbers of the GLFW\n  library.  It is intended for when you are using GLFW as a shared library and\n  want to ensure that you are using the minimum required version.\n\n  Any or all of the version arguments may be `NULL`.\n\n  @param[out] major Where to store the major version number, or `NULL`.\n  @param[out] minor Where to store the minor version number, or `NULL`.\n  @param[out] rev Where to store the revision number, or `NULL`.\n\n  @errors None.\n\n  @remark This function may be called before @ref glfwInit.\n\n  @thread_safety This function may be called from any thread.\n\n  @sa @ref intro_version\n  @sa @ref glfwGetVersionString\n\n  @since Added in version 1.0.\n\n  @ingroup init"]
     pub fn glfwGetVersion(
@@ -881,3 +912,27 @@ unsafe extern "C" {
     #[doc = " @brief Returns the Vulkan instance extensions required by GLFW.\n\n  This function returns an array of names of Vulkan instance extensions required\n  by GLFW for creating Vulkan surfaces for GLFW windows.  If successful, the\n  list will always contain `VK_KHR_surface`, so if you don't require any\n  additional extensions you can pass this list directly to the\n  `VkInstanceCreateInfo` struct.\n\n  If Vulkan is not available on the machine, this function returns `NULL` and\n  generates a @ref GLFW_API_UNAVAILABLE error.  Call @ref glfwVulkanSupported\n  to check whether Vulkan is at least minimally available.\n\n  If Vulkan is available but no set of extensions allowing window surface\n  creation was found, this function returns `NULL`.  You may still use Vulkan\n  for off-screen rendering and compute work.\n\n  @param[out] count Where to store the number of extensions in the returned\n  array.  This is set to zero if an error occurred.\n  @return An array of ASCII encoded extension names, or `NULL` if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED and @ref\n  GLFW_API_UNAVAILABLE.\n\n  @remark Additional extensions may be required by future versions of GLFW.\n  You should check if any extensions you wish to enable are already in the\n  returned array, as it is an error to specify an extension more than once in\n  the `VkInstanceCreateInfo` struct.\n\n  @pointer_lifetime The returned array is allocated and freed by GLFW.  You\n  should not free it yourself.  It is guaranteed to be valid only until the\n  library is terminated.\n\n  @thread_safety This function may be called from any thread.\n\n  @sa @ref vulkan_ext\n  @sa @ref glfwCreateWindowSurface\n\n  @since Added in version 3.2.\n\n  @ingroup vulkan"]
     pub fn glfwGetRequiredInstanceExtensions(count: *mut u32) -> *mut *const core::ffi::c_char;
 }
+unsafe extern "C" {
+    #[doc = " @brief Returns the address of the specified Vulkan instance function.\n\n  This function returns the address of the specified Vulkan core or extension\n  function for the specified instance.  If instance is set to `NULL` it can\n  return any function exported from the Vulkan loader, including at least the\n  following functions:\n\n  - `vkEnumerateInstanceExtensionProperties`\n  - `vkEnumerateInstanceLayerProperties`\n  - `vkCreateInstance`\n  - `vkGetInstanceProcAddr`\n\n  If Vulkan is not available on the machine, this function returns `NULL` and\n  generates a @ref GLFW_API_UNAVAILABLE error.  Call @ref glfwVulkanSupported\n  to check whether Vulkan is at least minimally available.\n\n  This function is equivalent to calling `vkGetInstanceProcAddr` with\n  a platform-specific query of the Vulkan loader as a fallback.\n\n  @param[in] instance The Vulkan instance to query, or `NULL` to retrieve\n  functions related to instance creation.\n  @param[in] procname The ASCII encoded name of the function.\n  @return The address of the function, or `NULL` if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED and @ref\n  GLFW_API_UNAVAILABLE.\n\n  @pointer_lifetime The returned function pointer is valid until the library\n  is terminated.\n\n  @thread_safety This function may be called from any thread.\n\n  @sa @ref vulkan_proc\n\n  @since Added in version 3.2.\n\n  @ingroup vulkan"]
+    pub fn glfwGetInstanceProcAddress(
+        instance: VkInstance,
+        procname: *const core::ffi::c_char,
+    ) -> GLFWvkproc;
+}
+unsafe extern "C" {
+    #[doc = " @brief Returns whether the specified queue family can present images.\n\n  This function returns whether the specified queue family of the specified\n  physical device supports presentation to the platform GLFW was built for.\n\n  If Vulkan or the required window surface creation instance extensions are\n  not available on the machine, or if the specified instance was not created\n  with the required extensions, this function returns `GLFW_FALSE` and\n  generates a @ref GLFW_API_UNAVAILABLE error.  Call @ref glfwVulkanSupported\n  to check whether Vulkan is at least minimally available and @ref\n  glfwGetRequiredInstanceExtensions to check what instance extensions are\n  required.\n\n  @param[in] instance The instance that the physical device belongs to.\n  @param[in] device The physical device that the queue family belongs to.\n  @param[in] queuefamily The index of the queue family to query.\n  @return `GLFW_TRUE` if the queue family supports presentation, or\n  `GLFW_FALSE` otherwise.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED, @ref\n  GLFW_API_UNAVAILABLE and @ref GLFW_PLATFORM_ERROR.\n\n  @remark @macos This function currently always returns `GLFW_TRUE`, as the\n  `VK_MVK_macos_surface` and `VK_EXT_metal_surface` extensions do not provide\n  a `vkGetPhysicalDevice*PresentationSupport` type function.\n\n  @thread_safety This function may be called from any thread.  For\n  synchronization details of Vulkan objects, see the Vulkan specification.\n\n  @sa @ref vulkan_present\n\n  @since Added in version 3.2.\n\n  @ingroup vulkan"]
+    pub fn glfwGetPhysicalDevicePresentationSupport(
+        instance: VkInstance,
+        device: VkPhysicalDevice,
+        queuefamily: u32,
+    ) -> core::ffi::c_int;
+}
+unsafe extern "C" {
+    #[doc = " @brief Creates a Vulkan surface for the specified window.\n\n  This function creates a Vulkan surface for the specified window.\n\n  If the Vulkan loader or at least one minimally functional ICD were not found,\n  this function returns `VK_ERROR_INITIALIZATION_FAILED` and generates a @ref\n  GLFW_API_UNAVAILABLE error.  Call @ref glfwVulkanSupported to check whether\n  Vulkan is at least minimally available.\n\n  If the required window surface creation instance extensions are not\n  available or if the specified instance was not created with these extensions\n  enabled, this function returns `VK_ERROR_EXTENSION_NOT_PRESENT` and\n  generates a @ref GLFW_API_UNAVAILABLE error.  Call @ref\n  glfwGetRequiredInstanceExtensions to check what instance extensions are\n  required.\n\n  The window surface cannot be shared with another API so the window must\n  have been created with the [client api hint](@ref GLFW_CLIENT_API_attrib)\n  set to `GLFW_NO_API` otherwise it generates a @ref GLFW_INVALID_VALUE error\n  and returns `VK_ERROR_NATIVE_WINDOW_IN_USE_KHR`.\n\n  The window surface must be destroyed before the specified Vulkan instance.\n  It is the responsibility of the caller to destroy the window surface.  GLFW\n  does not destroy it for you.  Call `vkDestroySurfaceKHR` to destroy the\n  surface.\n\n  @param[in] instance The Vulkan instance to create the surface in.\n  @param[in] window The window to create the surface for.\n  @param[in] allocator The allocator to use, or `NULL` to use the default\n  allocator.\n  @param[out] surface Where to store the handle of the surface.  This is set\n  to `VK_NULL_HANDLE` if an error occurred.\n  @return `VK_SUCCESS` if successful, or a Vulkan error code if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED, @ref\n  GLFW_API_UNAVAILABLE, @ref GLFW_PLATFORM_ERROR and @ref GLFW_INVALID_VALUE\n\n  @remark If an error occurs before the creation call is made, GLFW returns\n  the Vulkan error code most appropriate for the error.  Appropriate use of\n  @ref glfwVulkanSupported and @ref glfwGetRequiredInstanceExtensions should\n  eliminate almost all occurrences of these errors.\n\n  @thread_safety This function may be called from any thread.  For\n  synchronization details of Vulkan objects, see the Vulkan specification.\n\n  @sa @ref vulkan_surface\n  @sa @ref glfwGetRequiredInstanceExtensions\n\n  @since Added in version 3.2.\n\n  @ingroup vulkan"]
+    pub fn glfwCreateWindowSurface(
+        instance: VkInstance,
+        window: *mut GLFWwindow,
+        allocator: *const VkAllocationCallbacks,
+        surface: *mut VkSurfaceKHR,
+    ) -> VkResult;
+}
@@ -5,19 +5,20 @@ pub mod cocoa;
 pub mod win32;
 
 #[cfg(any(
-    all(
-        any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"),
-        feature = "wayland"
-    ),
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
     doc
 ))]
 pub mod wayland;
 
 #[cfg(any(
-    all(
-        any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"),
-        not(feature = "wayland")
-    ),
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
     doc
 ))]
 pub mod x11;
+
+// OSMesa is loaded at runtime by GLFW on every platform, so there is nothing to link.
+pub mod osmesa;
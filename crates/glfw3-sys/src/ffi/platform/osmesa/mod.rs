@@ -0,0 +1,29 @@
+/* automatically generated by rust-bindgen 0.71.1 */
+
+use crate::*;
+
+pub type OSMesaContext = *mut core::ffi::c_void;
+unsafe extern "C" {
+    #[doc = " @brief Retrieves the color buffer associated with the specified window.\n\n  @param[in] window The window whose color buffer to retrieve.\n  @param[out] width Where to store the width of the color buffer, or `NULL`.\n  @param[out] height Where to store the height of the color buffer, or `NULL`.\n  @param[out] format Where to store the OSMesa pixel format of the color\n  buffer, or `NULL`.\n  @param[out] buffer Where to store the address of the color buffer, or\n  `NULL`.\n  @return `GLFW_TRUE` if successful, or `GLFW_FALSE` if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED and @ref\n  GLFW_NO_WINDOW_CONTEXT.\n\n  @thread_safety This function may be called from any thread.  Access is not\n  synchronized.\n\n  @since Added in version 3.3.\n\n  @ingroup native"]
+    pub fn glfwGetOSMesaColorBuffer(
+        window: *mut GLFWwindow,
+        width: *mut core::ffi::c_int,
+        height: *mut core::ffi::c_int,
+        format: *mut core::ffi::c_int,
+        buffer: *mut *mut core::ffi::c_void,
+    ) -> core::ffi::c_int;
+}
+unsafe extern "C" {
+    #[doc = " @brief Retrieves the depth buffer associated with the specified window.\n\n  @param[in] window The window whose depth buffer to retrieve.\n  @param[out] width Where to store the width of the depth buffer, or `NULL`.\n  @param[out] height Where to store the height of the depth buffer, or `NULL`.\n  @param[out] bytesPerValue Where to store the number of bytes per depth\n  buffer element, or `NULL`.\n  @param[out] buffer Where to store the address of the depth buffer, or\n  `NULL`.\n  @return `GLFW_TRUE` if successful, or `GLFW_FALSE` if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED and @ref\n  GLFW_NO_WINDOW_CONTEXT.\n\n  @thread_safety This function may be called from any thread.  Access is not\n  synchronized.\n\n  @since Added in version 3.3.\n\n  @ingroup native"]
+    pub fn glfwGetOSMesaDepthBuffer(
+        window: *mut GLFWwindow,
+        width: *mut core::ffi::c_int,
+        height: *mut core::ffi::c_int,
+        bytesPerValue: *mut core::ffi::c_int,
+        buffer: *mut *mut core::ffi::c_void,
+    ) -> core::ffi::c_int;
+}
+unsafe extern "C" {
+    #[doc = " @brief Returns the `OSMesaContext` of the specified window.\n\n  @return The `OSMesaContext` of the specified window, or `NULL` if an\n  [error](@ref error_handling) occurred.\n\n  @errors Possible errors include @ref GLFW_NOT_INITIALIZED and @ref\n  GLFW_NO_WINDOW_CONTEXT.\n\n  @thread_safety This function may be called from any thread.  Access is not\n  synchronized.\n\n  @since Added in version 3.3.\n\n  @ingroup native"]
+    pub fn glfwGetOSMesaContext(window: *mut GLFWwindow) -> OSMesaContext;
+}
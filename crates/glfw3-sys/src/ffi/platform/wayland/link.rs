@@ -1,2 +0,0 @@
-#[link(name = "wayland-client")]
-extern "C" {}
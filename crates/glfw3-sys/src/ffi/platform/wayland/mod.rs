@@ -1,7 +1,6 @@
 /* automatically generated by rust-bindgen 0.71.1 */
 
 use crate::*;
-mod link;
 pub type wl_output = core::ffi::c_void;
 pub type wl_display = core::ffi::c_void;
 pub type wl_surface = core::ffi::c_void;
@@ -1,2 +0,0 @@
-#[link(name = "X11")]
-extern "C" {}
@@ -1,7 +1,6 @@
 /* automatically generated by rust-bindgen 0.71.1 */
 
 use crate::*;
-mod link;
 
 pub type Display = core::ffi::c_void;
 pub type RRCrtc = *mut core::ffi::c_void;
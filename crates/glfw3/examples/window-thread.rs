@@ -1,6 +1,6 @@
 use core::{ptr, time::Duration};
 
-use glfw3::{Glfw, WindowEvent};
+use glfw3::{Event, Glfw, WindowEvent};
 use glfw3_sys as sys;
 
 mod gl;
@@ -42,10 +42,10 @@ fn main() {
     let timeout = Duration::from_secs(1);
     let mut running = true;
     while running {
-        let result = glfw.wait_events_timeout(timeout, &mut |_window_id, (_time, event)| {
+        let result = glfw.wait_events_timeout(timeout, &mut |(_time, event)| {
             println!("{:?}", event);
             match event {
-                WindowEvent::Close => {
+                Event::Window(_, WindowEvent::Close) => {
                     running = false;
                 }
                 _ => {}
@@ -1,4 +1,4 @@
-use glfw3::{Glfw, Window, WindowEvent};
+use glfw3::{Event, Glfw, Window, WindowEvent};
 
 mod gl;
 use gl::{Gl, GL_COLOR_BUFFER_BIT};
@@ -19,13 +19,13 @@ fn main() {
 
     let mut running = true;
     while running {
-        let result = glfw.wait_events(&mut |_window_id, (_time, event)| {
+        let result = glfw.wait_events(&mut |(_time, event)| {
             println!("{:?}", event);
             match event {
-                WindowEvent::Close => {
+                Event::Window(_, WindowEvent::Close) => {
                     running = false;
                 }
-                WindowEvent::Refresh => {
+                Event::Window(_, WindowEvent::Refresh) => {
                     gl.clear_color(0.2, 0.2, 0.2, 0.2);
                     gl.clear(GL_COLOR_BUFFER_BIT);
                     window.swap_buffers().expect("glfwSwapBuffers");
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use crate::{GamepadAxis, GamepadState, Joystick, JoystickGuid};
+
+/// How positions near the center of a stick are discarded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeadZone {
+    /// Each axis on its own, which snaps nearly straight movement onto the axes.
+    Axial(f32),
+    /// The distance from the center, which preserves the direction of the stick.
+    Radial(f32),
+}
+
+/// Maps the magnitude left after the dead-zone, in `0.0..=1.0`, to the output.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseCurve {
+    Linear,
+    /// Finer control near the center.
+    Quadratic,
+    /// Linear interpolation between `(input, output)` points sorted by input,
+    /// starting at `(0.0, 0.0)` and ending at `(1.0, 1.0)`.
+    Custom(Vec<(f32, f32)>),
+}
+
+impl ResponseCurve {
+    pub fn apply(&self, input: f32) -> f32 {
+        let input = input.clamp(0.0, 1.0);
+        match self {
+            ResponseCurve::Linear => input,
+            ResponseCurve::Quadratic => input * input,
+            ResponseCurve::Custom(points) => {
+                let mut start = (0.0, 0.0);
+                for &end in points.iter().chain([(1.0, 1.0)].iter()) {
+                    if input <= end.0 {
+                        let span = end.0 - start.0;
+                        if span <= 0.0 {
+                            return end.1;
+                        }
+                        return start.1 + (end.1 - start.1) * (input - start.0) / span;
+                    }
+                    start = end;
+                }
+                1.0
+            }
+        }
+    }
+}
+
+/// Rescales a magnitude so that `dead_zone` maps to the start of the output
+/// range and `1.0` to its end, then applies the curve and anti-dead-zone.
+///
+/// Both zones are clamped to `0.0..=1.0`, where a dead-zone of `1.0` or more
+/// discards every position.
+fn shape(magnitude: f32, dead_zone: f32, anti_dead_zone: f32, curve: &ResponseCurve) -> f32 {
+    let dead_zone = dead_zone.clamp(0.0, 1.0);
+    let anti_dead_zone = anti_dead_zone.clamp(0.0, 1.0);
+    if magnitude <= dead_zone || dead_zone >= 1.0 {
+        return 0.0;
+    }
+    let scaled = ((magnitude - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    anti_dead_zone + (1.0 - anti_dead_zone) * curve.apply(scaled)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StickCalibration {
+    pub dead_zone: DeadZone,
+    /// The smallest magnitude reported outside the dead-zone, to overcome a
+    /// dead-zone applied later on, e.g. by a game.
+    pub anti_dead_zone: f32,
+    pub curve: ResponseCurve,
+}
+
+impl Default for StickCalibration {
+    fn default() -> Self {
+        StickCalibration {
+            dead_zone: DeadZone::Radial(0.1),
+            anti_dead_zone: 0.0,
+            curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+impl StickCalibration {
+    /// Processes a stick position. The result lies within the unit circle for
+    /// radial dead-zones and within the unit square for axial ones.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        match self.dead_zone {
+            DeadZone::Axial(dead_zone) => {
+                let axis = |value: f32| {
+                    let magnitude = shape(value.abs(), dead_zone, self.anti_dead_zone, &self.curve);
+                    magnitude.copysign(value)
+                };
+                (axis(x), axis(y))
+            }
+            DeadZone::Radial(dead_zone) => {
+                let length = x.hypot(y);
+                let magnitude = shape(length, dead_zone, self.anti_dead_zone, &self.curve);
+                if 0.0 == magnitude {
+                    (0.0, 0.0)
+                } else {
+                    (x / length * magnitude, y / length * magnitude)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriggerCalibration {
+    /// Measured from the released position, on a `0.0..=1.0` scale.
+    pub dead_zone: f32,
+    pub anti_dead_zone: f32,
+    pub curve: ResponseCurve,
+}
+
+impl Default for TriggerCalibration {
+    fn default() -> Self {
+        TriggerCalibration {
+            dead_zone: 0.05,
+            anti_dead_zone: 0.0,
+            curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+impl TriggerCalibration {
+    /// Processes a trigger position in GLFW's `-1.0..=1.0` range.
+    pub fn apply(&self, value: f32) -> f32 {
+        let pressed = (value + 1.0) / 2.0;
+        shape(pressed, self.dead_zone, self.anti_dead_zone, &self.curve) * 2.0 - 1.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationProfile {
+    pub left_stick: StickCalibration,
+    pub right_stick: StickCalibration,
+    pub left_trigger: TriggerCalibration,
+    pub right_trigger: TriggerCalibration,
+}
+
+impl CalibrationProfile {
+    pub fn apply(&self, state: &GamepadState) -> GamepadState {
+        let mut state = *state;
+        let sticks = [
+            (&self.left_stick, GamepadAxis::LeftX, GamepadAxis::LeftY),
+            (&self.right_stick, GamepadAxis::RightX, GamepadAxis::RightY),
+        ];
+        for (stick, x_axis, y_axis) in sticks {
+            let (x, y) = stick.apply(state.axis(x_axis), state.axis(y_axis));
+            state.set_axis(x_axis, x);
+            state.set_axis(y_axis, y);
+        }
+        let triggers = [
+            (&self.left_trigger, GamepadAxis::LeftTrigger),
+            (&self.right_trigger, GamepadAxis::RightTrigger),
+        ];
+        for (trigger, axis) in triggers {
+            state.set_axis(axis, trigger.apply(state.axis(axis)));
+        }
+        state
+    }
+}
+
+/// Calibration profiles for joystick models, keyed by GUID, with a fallback
+/// for models without a profile of their own.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    pub default: CalibrationProfile,
+    pub profiles: HashMap<JoystickGuid, CalibrationProfile>,
+}
+
+impl Calibration {
+    pub fn profile(&self, guid: Option<JoystickGuid>) -> &CalibrationProfile {
+        guid.and_then(|guid| self.profiles.get(&guid))
+            .unwrap_or(&self.default)
+    }
+}
+
+impl Joystick {
+    /// The gamepad state processed with the profile for this joystick's GUID.
+    pub fn calibrated_gamepad_state(&self, calibration: &Calibration) -> Option<GamepadState> {
+        let state = self.gamepad_state()?;
+        Some(calibration.profile(self.guid()).apply(&state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn assert_near(expected: (f32, f32), actual: (f32, f32)) {
+        assert!(
+            (expected.0 - actual.0).abs() < 1e-5 && (expected.1 - actual.1).abs() < 1e-5,
+            "expected {:?}, found {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn response_curve() {
+        assert_eq!(0.25, ResponseCurve::Quadratic.apply(0.5));
+        assert_eq!(1.0, ResponseCurve::Linear.apply(1.5));
+        let curve = ResponseCurve::Custom(vec![(0.5, 0.2)]);
+        assert_eq!(0.1, curve.apply(0.25));
+        assert_eq!(0.6, curve.apply(0.75));
+        assert_eq!(1.0, curve.apply(1.0));
+    }
+
+    #[test]
+    fn radial_dead_zone() {
+        let stick = StickCalibration {
+            dead_zone: DeadZone::Radial(0.2),
+            ..Default::default()
+        };
+        assert_eq!((0.0, 0.0), stick.apply(0.1, 0.1));
+        assert_near((0.0, -0.5), stick.apply(0.0, -0.6));
+        // Diagonals keep their direction and are clamped to the unit circle
+        let (x, y) = stick.apply(1.0, 1.0);
+        assert_near((x, x), (x, y));
+        assert_near((1.0, 0.0), (x.hypot(y), 0.0));
+    }
+
+    #[test]
+    fn axial_dead_zone() {
+        let stick = StickCalibration {
+            dead_zone: DeadZone::Axial(0.2),
+            ..Default::default()
+        };
+        assert_near((0.0, 0.5), stick.apply(0.15, 0.6));
+        assert_near((-1.0, 1.0), stick.apply(-1.0, 1.0));
+    }
+
+    #[test]
+    fn anti_dead_zone() {
+        let stick = StickCalibration {
+            dead_zone: DeadZone::Axial(0.2),
+            anti_dead_zone: 0.3,
+            curve: ResponseCurve::Quadratic,
+        };
+        assert_near((0.0, -0.3), stick.apply(0.0, -0.2001));
+        assert_near((0.475, 1.0), stick.apply(0.6, 1.0));
+    }
+
+    #[test]
+    fn out_of_range_zones() {
+        let mut stick = StickCalibration {
+            dead_zone: DeadZone::Radial(1.0),
+            ..Default::default()
+        };
+        assert_eq!((0.0, 0.0), stick.apply(1.0, 1.0));
+        stick.dead_zone = DeadZone::Axial(1.5);
+        assert_eq!((0.0, 0.0), stick.apply(1.0, -1.0));
+
+        stick.dead_zone = DeadZone::Axial(-0.5);
+        assert_near((0.5, -1.0), stick.apply(0.5, -1.0));
+        stick.anti_dead_zone = 2.0;
+        assert_near((1.0, 0.0), stick.apply(0.5, 0.0));
+        stick.anti_dead_zone = -1.0;
+        assert_near((0.25, 0.0), stick.apply(0.25, 0.0));
+
+        let trigger = TriggerCalibration {
+            dead_zone: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(-1.0, trigger.apply(1.0));
+    }
+
+    #[test]
+    fn trigger_calibration() {
+        let trigger = TriggerCalibration {
+            dead_zone: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(-1.0, trigger.apply(-0.2));
+        assert_eq!(0.0, trigger.apply(0.5));
+        assert_eq!(1.0, trigger.apply(1.0));
+    }
+
+    #[test]
+    fn calibration_profiles() {
+        let guid: JoystickGuid = "030000005e0400008e02000014010000".parse().unwrap();
+        let mut calibration = Calibration::default();
+        let mut profile = CalibrationProfile::default();
+        profile.left_stick.dead_zone = DeadZone::Radial(0.5);
+        calibration.profiles.insert(guid, profile);
+
+        let mut state = GamepadState::default();
+        state.set_axis(GamepadAxis::LeftX, 0.3);
+        state.set_axis(GamepadAxis::LeftTrigger, -1.0);
+        state.set_axis(GamepadAxis::RightTrigger, -1.0);
+        let calibrated = calibration.profile(Some(guid)).apply(&state);
+        assert_eq!(0.0, calibrated.axis(GamepadAxis::LeftX));
+        assert_eq!(-1.0, calibrated.axis(GamepadAxis::LeftTrigger));
+        let calibrated = calibration.profile(None).apply(&state);
+        assert!(calibrated.axis(GamepadAxis::LeftX) > 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_calibration() {
+        let guid: JoystickGuid = "030000005e0400008e02000014010000".parse().unwrap();
+        let mut calibration = Calibration::default();
+        let mut profile = CalibrationProfile::default();
+        profile.right_stick.curve = ResponseCurve::Custom(vec![(0.5, 0.25)]);
+        calibration.profiles.insert(guid, profile);
+
+        let json = serde_json::to_string(&calibration).unwrap();
+        assert!(json.contains("\"030000005e0400008e02000014010000\""));
+        assert_eq!(calibration, serde_json::from_str(&json).unwrap());
+    }
+}
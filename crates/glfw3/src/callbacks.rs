@@ -1,18 +1,20 @@
-use crate::{Action, Key, Modifiers, MouseButton, WindowEvent, WindowId};
+use crate::{
+    Action, Event, JoystickEvent, JoystickId, Key, Modifiers, MouseButton, Scancode, WindowEvent,
+    WindowId,
+};
 use core::ffi::{c_char, c_double, c_float, c_int, c_uint, CStr};
 use glfw3_sys as sys;
 use std::{cell::RefCell, marker::PhantomData, path::PathBuf};
 
 type CallbackPtr = *mut core::ffi::c_void;
 
-type HandlerFn = fn(
-    window_id: WindowId,
-    event: (f64, WindowEvent),
-    callback_ptr: CallbackPtr,
-) -> Option<(f64, WindowEvent)>;
+type HandlerFn = fn(event: (f64, Event), callback_ptr: CallbackPtr) -> Option<(f64, Event)>;
 
 thread_local! {
     static HANDLER: RefCell<Option<(HandlerFn, CallbackPtr)>> = RefCell::new(None);
+    /// The last cursor position of each window, from which motion is measured.
+    static CURSOR_POSITIONS: RefCell<Vec<(*mut sys::GLFWwindow, (f64, f64))>> =
+        const { RefCell::new(Vec::new()) };
 }
 
 pub struct UnsetHandlerGuard<'a, F> {
@@ -27,31 +29,57 @@ impl<'a, F> Drop for UnsetHandlerGuard<'a, F> {
     }
 }
 
-fn call_handler(window_id: WindowId, event: (f64, WindowEvent)) -> Option<(f64, WindowEvent)> {
+pub(crate) fn call_handler(event: (f64, Event)) -> Option<(f64, Event)> {
     HANDLER.with(|ref_cell| {
         if let Some((handler, callback_ptr)) = *ref_cell.borrow() {
-            handler(window_id, event, callback_ptr)
+            handler(event, callback_ptr)
         } else {
             Some(event)
         }
     })
 }
 
+fn call_window_handler(window: *mut sys::GLFWwindow, (time, event): (f64, WindowEvent)) {
+    call_handler((time, Event::Window(WindowId(window as usize), event)));
+}
+
+/// Records the cursor position of `window` and returns the motion since the
+/// previous one.
+fn cursor_moved(window: *mut sys::GLFWwindow, pos: (f64, f64)) -> Option<(f64, f64)> {
+    CURSOR_POSITIONS.with(|positions| {
+        let mut positions = positions.borrow_mut();
+        match positions.iter_mut().find(|(ptr, _)| *ptr == window) {
+            Some((_, last)) => {
+                let last = core::mem::replace(last, pos);
+                Some((pos.0 - last.0, pos.1 - last.1))
+            }
+            None => {
+                positions.push((window, pos));
+                None
+            }
+        }
+    })
+}
+
+/// Measures motion afresh from the next cursor position, so that a jump of
+/// the cursor is not reported as motion.
+pub(crate) fn reset_mouse_motion(window: *mut sys::GLFWwindow) {
+    // Avoid touching the registry during thread-local destruction
+    let _ = CURSOR_POSITIONS
+        .try_with(|positions| positions.borrow_mut().retain(|(ptr, _)| *ptr != window));
+}
+
 pub fn set_handler<'a, F>(callback: &'a mut F) -> UnsetHandlerGuard<'a, F>
 where
-    F: FnMut(WindowId, (f64, WindowEvent)) -> Option<(f64, WindowEvent)>,
+    F: FnMut((f64, Event)) -> Option<(f64, Event)>,
 {
-    fn handler<F>(
-        window_id: WindowId,
-        event: (f64, WindowEvent),
-        callback_ptr: CallbackPtr,
-    ) -> Option<(f64, WindowEvent)>
+    fn handler<F>(event: (f64, Event), callback_ptr: CallbackPtr) -> Option<(f64, Event)>
     where
-        F: FnMut(WindowId, (f64, WindowEvent)) -> Option<(f64, WindowEvent)>,
+        F: FnMut((f64, Event)) -> Option<(f64, Event)>,
     {
         unsafe {
             let callback: &mut F = &mut *(callback_ptr as *mut F);
-            callback(window_id, event)
+            callback(event)
         }
     }
 
@@ -68,7 +96,7 @@ where
 unsafe extern "C" fn window_refresh_callback(window: *mut sys::GLFWwindow) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Refresh);
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn key_callback(
@@ -81,11 +109,12 @@ unsafe extern "C" fn key_callback(
     let time = sys::glfwGetTime();
     let key = Key::try_from(key);
     let action = Action::try_from(action);
+    let scancode = Scancode(scancode);
     let mods = Modifiers::from_bits_truncate(mods);
     match (key, action) {
         (Ok(key), Ok(action)) => {
             let event = (time, WindowEvent::Key(key, scancode, action, mods));
-            call_handler(WindowId(window as usize), event);
+            call_window_handler(window, event);
         }
         (Err(key), Ok(_)) => {
             log::warn!("ignoring unidentified key: {}", key);
@@ -110,7 +139,7 @@ unsafe extern "C" fn key_callback(
 unsafe extern "C" fn char_callback(window: *mut sys::GLFWwindow, codepoint: c_uint) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Char(codepoint));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn char_mods_callback(
@@ -122,7 +151,20 @@ unsafe extern "C" fn char_mods_callback(
     let mods = Modifiers::from_bits_truncate(mods);
     #[allow(deprecated)]
     let event = (time, WindowEvent::CharModifiers(codepoint, mods));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
+}
+
+/// Unix paths are arbitrary bytes, which are kept as they are.
+#[cfg(unix)]
+fn path_from_c_str(path: &CStr) -> Option<PathBuf> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    Some(PathBuf::from(OsStr::from_bytes(path.to_bytes())))
+}
+
+/// GLFW reports paths as UTF-8 on other platforms.
+#[cfg(not(unix))]
+fn path_from_c_str(path: &CStr) -> Option<PathBuf> {
+    path.to_str().ok().map(PathBuf::from)
 }
 
 unsafe extern "C" fn drop_callback(
@@ -134,15 +176,15 @@ unsafe extern "C" fn drop_callback(
     let mut filepaths = Vec::with_capacity(count as usize);
 
     for i in 0..count as isize {
-        if let Ok(path) = CStr::from_ptr(*paths.offset(i)).to_str() {
-            filepaths.push(PathBuf::from(path));
+        if let Some(path) = path_from_c_str(CStr::from_ptr(*paths.offset(i))) {
+            filepaths.push(path);
         } else {
             log::warn!("file drop callback received invalid path");
         }
     }
 
     let event = (time, WindowEvent::FileDrop(filepaths));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn scroll_callback(
@@ -152,7 +194,7 @@ unsafe extern "C" fn scroll_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Scroll(xoffset, yoffset));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn cursor_position_callback(
@@ -162,7 +204,12 @@ unsafe extern "C" fn cursor_position_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::CursorPos(xpos, ypos));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
+    if let Some((dx, dy)) = cursor_moved(window, (xpos, ypos)) {
+        if dx != 0.0 || dy != 0.0 {
+            call_window_handler(window, (time, WindowEvent::MouseMotion(dx, dy)));
+        }
+    }
 }
 
 unsafe extern "C" fn window_position_callback(
@@ -172,7 +219,7 @@ unsafe extern "C" fn window_position_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Pos(xpos, ypos));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn window_size_callback(
@@ -182,13 +229,16 @@ unsafe extern "C" fn window_size_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Size(width, height));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn cursor_entered_callback(window: *mut sys::GLFWwindow, entered: c_int) {
     let time = sys::glfwGetTime();
+    if entered != 0 {
+        reset_mouse_motion(window);
+    }
     let event = (time, WindowEvent::CursorEnter(entered != 0));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn mouse_button_callback(
@@ -204,7 +254,7 @@ unsafe extern "C" fn mouse_button_callback(
     match (button, action) {
         (Ok(button), Ok(action)) => {
             let event = (time, WindowEvent::MouseButton(button, action, mods));
-            call_handler(WindowId(window as usize), event);
+            call_window_handler(window, event);
         }
         (Err(key), Ok(_)) => {
             log::warn!("ignoring unidentified mouse button: {}", key);
@@ -229,19 +279,20 @@ unsafe extern "C" fn mouse_button_callback(
 unsafe extern "C" fn window_close_callback(window: *mut sys::GLFWwindow) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Close);
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn window_focus_callback(window: *mut sys::GLFWwindow, focused: c_int) {
     let time = sys::glfwGetTime();
+    reset_mouse_motion(window);
     let event = (time, WindowEvent::Focus(focused != 0));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn window_iconify_callback(window: *mut sys::GLFWwindow, iconify: c_int) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Iconify(iconify != 0));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn framebuffer_size_callback(
@@ -251,7 +302,7 @@ unsafe extern "C" fn framebuffer_size_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::FramebufferSize(width, height));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn content_scale_callback(
@@ -261,13 +312,13 @@ unsafe extern "C" fn content_scale_callback(
 ) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::ContentScale(xscale, yscale));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 unsafe extern "C" fn window_maximize_callback(window: *mut sys::GLFWwindow, maximized: c_int) {
     let time = sys::glfwGetTime();
     let event = (time, WindowEvent::Maximize(maximized != 0));
-    call_handler(WindowId(window as usize), event);
+    call_window_handler(window, event);
 }
 
 pub unsafe fn set_window_callbacks(window: *mut sys::GLFWwindow) {
@@ -289,3 +340,47 @@ pub unsafe fn set_window_callbacks(window: *mut sys::GLFWwindow) {
     sys::glfwSetWindowContentScaleCallback(window, Some(content_scale_callback));
     sys::glfwSetWindowMaximizeCallback(window, Some(window_maximize_callback));
 }
+
+pub unsafe extern "C" fn joystick_callback(jid: c_int, event: c_int) {
+    let time = sys::glfwGetTime();
+    let event = match (JoystickId::try_from(jid), event) {
+        (Ok(id), sys::GLFW_CONNECTED) => JoystickEvent::Connected(id),
+        (Ok(id), sys::GLFW_DISCONNECTED) => JoystickEvent::Disconnected(id),
+        _ => {
+            log::warn!(
+                "ignoring unidentified joystick event: jid = {}, event = {}",
+                jid,
+                event
+            );
+            return;
+        }
+    };
+    call_handler((time, Event::Joystick(event)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cursor_moved, path_from_c_str, reset_mouse_motion, sys};
+
+    #[test]
+    fn mouse_motion() {
+        let window = core::ptr::dangling_mut::<sys::GLFWwindow>();
+        let other = window.cast::<u8>().wrapping_add(1).cast();
+        assert_eq!(None, cursor_moved(window, (10.0, 10.0)));
+        assert_eq!(None, cursor_moved(other, (0.0, 0.0)));
+        assert_eq!(Some((5.0, -2.0)), cursor_moved(window, (15.0, 8.0)));
+        assert_eq!(Some((1.0, 1.0)), cursor_moved(other, (1.0, 1.0)));
+
+        reset_mouse_motion(window);
+        assert_eq!(None, cursor_moved(window, (500.0, 500.0)));
+        assert_eq!(Some((-1.0, 0.0)), cursor_moved(window, (499.0, 500.0)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+        let path = path_from_c_str(c"/tmp/caf\xe9.txt").unwrap();
+        assert_eq!(b"/tmp/caf\xe9.txt", path.as_os_str().as_bytes());
+    }
+}
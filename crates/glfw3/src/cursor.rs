@@ -0,0 +1,262 @@
+use core::{cell::RefCell, ptr, time::Duration};
+use std::rc::Rc;
+
+use glfw3_sys as sys;
+
+use crate::{unknown_error, Error, Glfw, Image, Terminate, Window};
+
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StandardCursor {
+    Arrow = sys::GLFW_ARROW_CURSOR,
+    IBeam = sys::GLFW_IBEAM_CURSOR,
+    Crosshair = sys::GLFW_CROSSHAIR_CURSOR,
+    PointingHand = sys::GLFW_POINTING_HAND_CURSOR,
+    ResizeEw = sys::GLFW_RESIZE_EW_CURSOR,
+    ResizeNs = sys::GLFW_RESIZE_NS_CURSOR,
+    /// May be unavailable, see [`StandardCursor::fallback`].
+    ResizeNwse = sys::GLFW_RESIZE_NWSE_CURSOR,
+    /// May be unavailable, see [`StandardCursor::fallback`].
+    ResizeNesw = sys::GLFW_RESIZE_NESW_CURSOR,
+    /// May be unavailable, see [`StandardCursor::fallback`].
+    ResizeAll = sys::GLFW_RESIZE_ALL_CURSOR,
+    /// May be unavailable, see [`StandardCursor::fallback`].
+    NotAllowed = sys::GLFW_NOT_ALLOWED_CURSOR,
+}
+
+impl StandardCursor {
+    /// A shape that is available on every platform to use in place of one
+    /// that is not, or `None` if this shape is always available.
+    pub fn fallback(self) -> Option<StandardCursor> {
+        match self {
+            StandardCursor::ResizeNwse | StandardCursor::ResizeNesw | StandardCursor::ResizeAll => {
+                Some(StandardCursor::Crosshair)
+            }
+            StandardCursor::NotAllowed => Some(StandardCursor::Arrow),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CreateCursorError {
+    /// The platform or cursor theme does not provide the shape.
+    Unavailable(StandardCursor),
+    CreateCursor(Error),
+}
+
+/// A cursor image that can be shown over a window with [`Window::set_cursor`].
+#[derive(Debug)]
+pub struct Cursor {
+    pub(crate) cursor_ptr: *mut sys::GLFWcursor,
+    pub(crate) _terminate: Rc<Terminate>,
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        unsafe {
+            sys::glfwDestroyCursor(self.cursor_ptr);
+            if let Some(err) = Glfw::get_error().err() {
+                log::warn!("glfwDestroyCursor failed: {:?}", err);
+            }
+        }
+    }
+}
+
+impl Glfw {
+    /// Creates a cursor from an RGBA image, with the hotspot in pixels from
+    /// the top-left corner of the image.
+    #[doc(alias = "glfwCreateCursor")]
+    pub fn create_cursor(&self, image: &Image, xhot: i32, yhot: i32) -> Result<Cursor, Error> {
+        unsafe {
+            let image = image.as_glfw_image();
+            let cursor_ptr = sys::glfwCreateCursor(&image, xhot, yhot);
+            Glfw::get_error()?;
+            if cursor_ptr.is_null() {
+                return Err(unknown_error());
+            }
+            Ok(Cursor {
+                cursor_ptr,
+                _terminate: Rc::clone(&self.terminate),
+            })
+        }
+    }
+
+    #[doc(alias = "glfwCreateStandardCursor")]
+    pub fn create_standard_cursor(
+        &self,
+        shape: StandardCursor,
+    ) -> Result<Cursor, CreateCursorError> {
+        unsafe {
+            let cursor_ptr = sys::glfwCreateStandardCursor(shape as i32);
+            match Glfw::get_error() {
+                Err(err) if sys::GLFW_CURSOR_UNAVAILABLE == err.code => {
+                    Err(CreateCursorError::Unavailable(shape))
+                }
+                Err(err) => Err(CreateCursorError::CreateCursor(err)),
+                Ok(()) if cursor_ptr.is_null() => {
+                    Err(CreateCursorError::CreateCursor(unknown_error()))
+                }
+                Ok(()) => Ok(Cursor {
+                    cursor_ptr,
+                    _terminate: Rc::clone(&self.terminate),
+                }),
+            }
+        }
+    }
+
+    /// Like [`Glfw::create_standard_cursor`], but falls back to a shape that
+    /// is always available when the requested one is not.
+    pub fn create_standard_cursor_or_fallback(
+        &self,
+        shape: StandardCursor,
+    ) -> Result<Cursor, CreateCursorError> {
+        match (self.create_standard_cursor(shape), shape.fallback()) {
+            (Err(CreateCursorError::Unavailable(_)), Some(fallback)) => {
+                log::debug!("{:?} cursor is unavailable, using {:?}", shape, fallback);
+                self.create_standard_cursor(fallback)
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+/// One image of an [`AnimatedCursor`].
+#[derive(Debug, Clone, Copy)]
+pub struct CursorFrame<'a> {
+    pub image: &'a Image,
+    pub xhot: i32,
+    pub yhot: i32,
+    pub duration: Duration,
+}
+
+/// A looping sequence of cursors, see [`Window::set_animated_cursor`].
+#[derive(Debug, Clone)]
+pub struct AnimatedCursor {
+    cursors: Rc<[Cursor]>,
+    durations: Rc<[f64]>,
+}
+
+struct Animation {
+    window_ptr: *mut sys::GLFWwindow,
+    cursor: AnimatedCursor,
+    start: f64,
+    current: usize,
+}
+
+thread_local! {
+    static ANIMATIONS: RefCell<Vec<Animation>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Finds the frame shown `elapsed` seconds into a looping animation and the
+/// seconds remaining until the next frame.
+pub(crate) fn frame_at(durations: &[f64], elapsed: f64) -> (usize, Option<f64>) {
+    let total: f64 = durations.iter().sum();
+    if durations.len() < 2 || total <= 0.0 {
+        return (0, None);
+    }
+    let elapsed = elapsed.max(0.0) % total;
+    let mut end = 0.0;
+    for (index, duration) in durations.iter().enumerate() {
+        end += duration;
+        if elapsed < end {
+            return (index, Some(end - elapsed));
+        }
+    }
+    (0, Some(durations[0]))
+}
+
+/// Switches animated cursors to their current frames, returning the seconds
+/// until the next switch is due.
+pub(crate) fn animate_cursors() -> Option<f64> {
+    ANIMATIONS.with(|animations| {
+        let mut animations = animations.borrow_mut();
+        if animations.is_empty() {
+            return None;
+        }
+        let now = unsafe { sys::glfwGetTime() };
+        let mut next_frame: Option<f64> = None;
+        for animation in animations.iter_mut() {
+            let (index, remaining) = frame_at(&animation.cursor.durations, now - animation.start);
+            if index != animation.current {
+                animation.current = index;
+                let cursor_ptr = animation.cursor.cursors[index].cursor_ptr;
+                unsafe { sys::glfwSetCursor(animation.window_ptr, cursor_ptr) };
+                if let Err(err) = Glfw::get_error() {
+                    log::debug!("glfwSetCursor failed: {:?}", err);
+                }
+            }
+            if let Some(remaining) = remaining {
+                next_frame = Some(next_frame.map_or(remaining, |next| next.min(remaining)));
+            }
+        }
+        next_frame
+    })
+}
+
+pub(crate) fn stop_animation(window_ptr: *mut sys::GLFWwindow) {
+    // Avoid touching the registry during thread-local destruction
+    let _ = ANIMATIONS.try_with(|animations| {
+        animations
+            .borrow_mut()
+            .retain(|animation| animation.window_ptr != window_ptr)
+    });
+}
+
+impl Glfw {
+    #[doc(alias = "glfwCreateCursor")]
+    pub fn create_animated_cursor(
+        &self,
+        frames: &[CursorFrame<'_>],
+    ) -> Result<AnimatedCursor, Error> {
+        if frames.is_empty() {
+            return Err(Error {
+                code: sys::GLFW_INVALID_VALUE,
+                desc: String::from("An animated cursor needs at least one frame"),
+            });
+        }
+        let cursors = frames
+            .iter()
+            .map(|frame| self.create_cursor(frame.image, frame.xhot, frame.yhot))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let durations = frames.iter().map(|frame| frame.duration.as_secs_f64());
+        Ok(AnimatedCursor {
+            cursors: cursors.into(),
+            durations: durations.collect(),
+        })
+    }
+}
+
+impl Window {
+    /// Sets the cursor shown over the content area, or restores the default
+    /// arrow with `None`. Stops any animated cursor.
+    ///
+    /// Dropping the cursor while it is set also restores the default arrow.
+    #[doc(alias = "glfwSetCursor")]
+    pub fn set_cursor(&self, cursor: Option<&Cursor>) -> Result<(), Error> {
+        stop_animation(self.window_ptr);
+        unsafe {
+            let cursor_ptr = cursor.map(|c| c.cursor_ptr).unwrap_or(ptr::null_mut());
+            sys::glfwSetCursor(self.window_ptr, cursor_ptr);
+            Glfw::get_error()
+        }
+    }
+
+    /// Shows an animated cursor, starting from its first frame.
+    ///
+    /// Frames are switched from within [`Glfw::poll_events`], [`Glfw::wait_events`]
+    /// and [`Glfw::wait_events_timeout`]. While an animation is running, the
+    /// waiting functions return in time for the next frame even if no events
+    /// arrived.
+    pub fn set_animated_cursor(&self, cursor: &AnimatedCursor) -> Result<(), Error> {
+        self.set_cursor(Some(&cursor.cursors[0]))?;
+        let animation = Animation {
+            window_ptr: self.window_ptr,
+            cursor: cursor.clone(),
+            start: unsafe { sys::glfwGetTime() },
+            current: 0,
+        };
+        ANIMATIONS.with(|animations| animations.borrow_mut().push(animation));
+        Ok(())
+    }
+}
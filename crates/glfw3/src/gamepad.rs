@@ -0,0 +1,290 @@
+use core::mem::MaybeUninit;
+use std::{fs, io, path::Path};
+
+use glfw3_sys as sys;
+
+use crate::{
+    joystick::{clear_joystick_error, joystick_string},
+    text_to_c_string, Action, Error, GamepadMapping, Glfw, Joystick, JoystickId,
+};
+
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+    A = sys::GLFW_GAMEPAD_BUTTON_A,
+    B = sys::GLFW_GAMEPAD_BUTTON_B,
+    X = sys::GLFW_GAMEPAD_BUTTON_X,
+    Y = sys::GLFW_GAMEPAD_BUTTON_Y,
+    LeftBumper = sys::GLFW_GAMEPAD_BUTTON_LEFT_BUMPER,
+    RightBumper = sys::GLFW_GAMEPAD_BUTTON_RIGHT_BUMPER,
+    Back = sys::GLFW_GAMEPAD_BUTTON_BACK,
+    Start = sys::GLFW_GAMEPAD_BUTTON_START,
+    Guide = sys::GLFW_GAMEPAD_BUTTON_GUIDE,
+    LeftThumb = sys::GLFW_GAMEPAD_BUTTON_LEFT_THUMB,
+    RightThumb = sys::GLFW_GAMEPAD_BUTTON_RIGHT_THUMB,
+    DpadUp = sys::GLFW_GAMEPAD_BUTTON_DPAD_UP,
+    DpadRight = sys::GLFW_GAMEPAD_BUTTON_DPAD_RIGHT,
+    DpadDown = sys::GLFW_GAMEPAD_BUTTON_DPAD_DOWN,
+    DpadLeft = sys::GLFW_GAMEPAD_BUTTON_DPAD_LEFT,
+}
+
+impl GamepadButton {
+    pub const CROSS: GamepadButton = GamepadButton::A;
+    pub const CIRCLE: GamepadButton = GamepadButton::B;
+    pub const SQUARE: GamepadButton = GamepadButton::X;
+    pub const TRIANGLE: GamepadButton = GamepadButton::Y;
+
+    pub fn all() -> impl Iterator<Item = GamepadButton> {
+        (0..=sys::GLFW_GAMEPAD_BUTTON_LAST)
+            .map(|value| unsafe { core::mem::transmute::<i32, GamepadButton>(value) })
+    }
+}
+
+impl TryFrom<i32> for GamepadButton {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        if (0..=sys::GLFW_GAMEPAD_BUTTON_LAST).contains(&value) {
+            return Ok(unsafe { core::mem::transmute::<i32, GamepadButton>(value) });
+        }
+        Err(value)
+    }
+}
+
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadAxis {
+    LeftX = sys::GLFW_GAMEPAD_AXIS_LEFT_X,
+    LeftY = sys::GLFW_GAMEPAD_AXIS_LEFT_Y,
+    RightX = sys::GLFW_GAMEPAD_AXIS_RIGHT_X,
+    RightY = sys::GLFW_GAMEPAD_AXIS_RIGHT_Y,
+    LeftTrigger = sys::GLFW_GAMEPAD_AXIS_LEFT_TRIGGER,
+    RightTrigger = sys::GLFW_GAMEPAD_AXIS_RIGHT_TRIGGER,
+}
+
+impl GamepadAxis {
+    pub fn all() -> impl Iterator<Item = GamepadAxis> {
+        (0..=sys::GLFW_GAMEPAD_AXIS_LAST)
+            .map(|value| unsafe { core::mem::transmute::<i32, GamepadAxis>(value) })
+    }
+}
+
+impl TryFrom<i32> for GamepadAxis {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        if (0..=sys::GLFW_GAMEPAD_AXIS_LAST).contains(&value) {
+            return Ok(unsafe { core::mem::transmute::<i32, GamepadAxis>(value) });
+        }
+        Err(value)
+    }
+}
+
+const BUTTON_COUNT: usize = sys::GLFW_GAMEPAD_BUTTON_LAST as usize + 1;
+const AXIS_COUNT: usize = sys::GLFW_GAMEPAD_AXIS_LAST as usize + 1;
+
+/// The inputs of a joystick remapped to an Xbox-like gamepad. Buttons and
+/// axes the device lacks stay released and at `0.0`.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct GamepadState {
+    buttons: [bool; BUTTON_COUNT],
+    axes: [f32; AXIS_COUNT],
+}
+
+impl GamepadState {
+    pub fn new(buttons: [bool; BUTTON_COUNT], axes: [f32; AXIS_COUNT]) -> GamepadState {
+        GamepadState { buttons, axes }
+    }
+
+    pub fn button(&self, button: GamepadButton) -> bool {
+        self.buttons[button as usize]
+    }
+
+    pub fn set_button(&mut self, button: GamepadButton, pressed: bool) {
+        self.buttons[button as usize] = pressed;
+    }
+
+    pub fn action(&self, button: GamepadButton) -> Action {
+        if self.button(button) {
+            Action::Press
+        } else {
+            Action::Release
+        }
+    }
+
+    /// Sticks range over `-1.0..=1.0` with +Y pointing down, triggers over
+    /// `-1.0..=1.0` from released to fully pressed.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+
+    pub fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes[axis as usize] = value;
+    }
+
+    pub fn buttons(&self) -> &[bool; BUTTON_COUNT] {
+        &self.buttons
+    }
+
+    pub fn axes(&self) -> &[f32; AXIS_COUNT] {
+        &self.axes
+    }
+}
+
+impl From<sys::GLFWgamepadstate> for GamepadState {
+    fn from(state: sys::GLFWgamepadstate) -> Self {
+        GamepadState {
+            buttons: state.buttons.map(|button| sys::GLFW_PRESS == button as i32),
+            axes: state.axes,
+        }
+    }
+}
+
+pub(crate) fn gamepad_state(id: JoystickId) -> Option<GamepadState> {
+    unsafe {
+        let mut state = MaybeUninit::uninit();
+        let result = sys::glfwGetGamepadState(id as i32, state.as_mut_ptr());
+        clear_joystick_error("glfwGetGamepadState");
+        if sys::GLFW_TRUE == result {
+            Some(state.assume_init().into())
+        } else {
+            None
+        }
+    }
+}
+
+impl Joystick {
+    /// Whether the joystick is present and has a gamepad mapping.
+    #[doc(alias = "glfwJoystickIsGamepad")]
+    pub fn is_gamepad(&self) -> bool {
+        let is_gamepad = unsafe { sys::glfwJoystickIsGamepad(self.id() as i32) };
+        clear_joystick_error("glfwJoystickIsGamepad");
+        sys::GLFW_TRUE == is_gamepad
+    }
+
+    /// The name from the gamepad mapping, which may differ from [`Joystick::name`].
+    #[doc(alias = "glfwGetGamepadName")]
+    pub fn gamepad_name(&self) -> Option<String> {
+        unsafe {
+            let name_ptr = sys::glfwGetGamepadName(self.id() as i32);
+            clear_joystick_error("glfwGetGamepadName");
+            joystick_string(name_ptr)
+        }
+    }
+
+    #[doc(alias = "glfwGetGamepadState")]
+    pub fn gamepad_state(&self) -> Option<GamepadState> {
+        gamepad_state(self.id())
+    }
+}
+
+/// A line of a gamepad mapping database that was rejected, numbered from 1.
+#[derive(Debug)]
+pub struct MappingLineError {
+    pub line: usize,
+    pub error: Error,
+}
+
+#[derive(Debug)]
+pub enum LoadMappingsError {
+    Io(io::Error),
+    Mappings(Vec<MappingLineError>),
+}
+
+fn update_gamepad_mapping(mapping: &str) -> Result<(), Error> {
+    // GLFW copies each line into a 1024 byte buffer and ignores longer ones
+    if mapping.len() >= 1024 {
+        return Err(Error {
+            code: sys::GLFW_INVALID_VALUE,
+            desc: "mapping is longer than 1023 bytes".into(),
+        });
+    }
+    let mapping = text_to_c_string(mapping)?;
+    unsafe {
+        sys::glfwUpdateGamepadMappings(mapping.as_ptr());
+        Glfw::get_error()
+    }
+}
+
+impl Glfw {
+    /// Adds the mapping, replacing any existing mapping for the same GUID.
+    #[doc(alias = "glfwUpdateGamepadMappings")]
+    pub fn update_gamepad_mapping(&self, mapping: &GamepadMapping) -> Result<(), Error> {
+        update_gamepad_mapping(&mapping.to_string())
+    }
+
+    /// Adds SDL_GameControllerDB mappings, one per line, replacing any existing
+    /// mapping for the same GUID. Blank lines and `#` comments are skipped.
+    ///
+    /// The valid lines are applied even if others are rejected. Mappings for
+    /// other platforms are accepted and ignored.
+    #[doc(alias = "glfwUpdateGamepadMappings")]
+    pub fn update_gamepad_mappings(&self, mappings: &str) -> Result<(), Vec<MappingLineError>> {
+        let errors: Vec<_> = mappings
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                // GLFW silently skips most malformed mappings, so they are
+                // parsed here first to be able to report them
+                let result = match line.parse::<GamepadMapping>() {
+                    Ok(_) => update_gamepad_mapping(line),
+                    Err(err) => Err(Error {
+                        code: sys::GLFW_INVALID_VALUE,
+                        desc: err.to_string(),
+                    }),
+                };
+                result.err().map(|error| MappingLineError {
+                    line: index + 1,
+                    error,
+                })
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Reads a `gamecontrollerdb.txt` file, such as the one from
+    /// <https://github.com/mdqinc/SDL_GameControllerDB>, and applies it with
+    /// [`Glfw::update_gamepad_mappings`].
+    pub fn load_gamepad_mappings_from_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), LoadMappingsError> {
+        let mappings = fs::read_to_string(path).map_err(LoadMappingsError::Io)?;
+        self.update_gamepad_mappings(&mappings)
+            .map_err(LoadMappingsError::Mappings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn gamepad_state() {
+        let mut raw = glfw3_sys::GLFWgamepadstate {
+            buttons: [0; 15],
+            axes: [0.0; 6],
+        };
+        raw.buttons[GamepadButton::Start as usize] = glfw3_sys::GLFW_PRESS as u8;
+        raw.axes[GamepadAxis::RightTrigger as usize] = 1.0;
+
+        let state = GamepadState::from(raw);
+        assert!(state.button(GamepadButton::Start));
+        assert!(!state.button(GamepadButton::CROSS));
+        assert_eq!(Action::Press, state.action(GamepadButton::Start));
+        assert_eq!(1.0, state.axis(GamepadAxis::RightTrigger));
+        assert_eq!(0.0, state.axis(GamepadAxis::LeftX));
+        assert_eq!(15, GamepadButton::all().count());
+        assert_eq!(6, GamepadAxis::all().count());
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{Action, MouseButton, WindowEvent};
+
+/// Distances are in screen coordinates and durations in seconds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GestureConfig {
+    /// The longest time between two presses of a multi-click.
+    pub multi_click_interval: f64,
+    /// The farthest two presses of a multi-click may be apart.
+    pub multi_click_distance: f64,
+    /// How far the cursor moves while pressed before a drag starts.
+    pub drag_threshold: f64,
+    /// How long a press is held in place to become a long-press.
+    pub long_press_duration: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            multi_click_interval: 0.5,
+            multi_click_distance: 4.0,
+            drag_threshold: 4.0,
+            long_press_duration: 0.6,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Gesture {
+    /// A press and release without a drag or long-press. `count` is 2 for a
+    /// double click, 3 for a triple click and so on.
+    Click {
+        button: MouseButton,
+        count: u32,
+        pos: (f64, f64),
+    },
+    DragStart {
+        button: MouseButton,
+        /// Where the button was pressed.
+        pos: (f64, f64),
+    },
+    DragMove {
+        button: MouseButton,
+        pos: (f64, f64),
+        delta: (f64, f64),
+    },
+    DragEnd {
+        button: MouseButton,
+        pos: (f64, f64),
+    },
+    LongPress {
+        button: MouseButton,
+        pos: (f64, f64),
+    },
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Press {
+    button: MouseButton,
+    pos: (f64, f64),
+    time: f64,
+    count: u32,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Derives clicks, drags and long-presses from the `MouseButton` and
+/// `CursorPos` events of a window.
+///
+/// Long-presses are detected on the next event, or on [`GestureRecognizer::update`]
+/// which should be called once per frame while no events arrive.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    cursor_pos: (f64, f64),
+    press: Option<Press>,
+    /// The previous press, which a new one may continue as a multi-click.
+    last_press: Option<Press>,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> GestureRecognizer {
+        GestureRecognizer {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Whether a drag is in progress.
+    pub fn dragging(&self) -> bool {
+        self.press.is_some_and(|press| press.dragging)
+    }
+
+    /// Reports a long-press once the button has been held long enough.
+    pub fn update(&mut self, time: f64) -> Option<Gesture> {
+        let press = self.press.as_mut()?;
+        if press.dragging || press.long_pressed {
+            return None;
+        }
+        if time - press.time >= self.config.long_press_duration {
+            press.long_pressed = true;
+            return Some(Gesture::LongPress {
+                button: press.button,
+                pos: press.pos,
+            });
+        }
+        None
+    }
+
+    pub fn handle_event(&mut self, (time, event): &(f64, WindowEvent)) -> Vec<Gesture> {
+        let mut gestures: Vec<Gesture> = self.update(*time).into_iter().collect();
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                let last_pos = core::mem::replace(&mut self.cursor_pos, (x, y));
+                let Some(press) = self.press.as_mut() else {
+                    return gestures;
+                };
+                if !press.dragging && distance(press.pos, (x, y)) > self.config.drag_threshold {
+                    press.dragging = true;
+                    gestures.push(Gesture::DragStart {
+                        button: press.button,
+                        pos: press.pos,
+                    });
+                    gestures.push(Gesture::DragMove {
+                        button: press.button,
+                        pos: (x, y),
+                        delta: (x - press.pos.0, y - press.pos.1),
+                    });
+                } else if press.dragging {
+                    gestures.push(Gesture::DragMove {
+                        button: press.button,
+                        pos: (x, y),
+                        delta: (x - last_pos.0, y - last_pos.1),
+                    });
+                }
+            }
+            WindowEvent::MouseButton(button, Action::Press, _) if self.press.is_none() => {
+                let pos = self.cursor_pos;
+                let count = match self.last_press {
+                    Some(last)
+                        if last.button == button
+                            && time - last.time <= self.config.multi_click_interval
+                            && distance(last.pos, pos) <= self.config.multi_click_distance =>
+                    {
+                        last.count + 1
+                    }
+                    _ => 1,
+                };
+                self.press = Some(Press {
+                    button,
+                    pos,
+                    time: *time,
+                    count,
+                    dragging: false,
+                    long_pressed: false,
+                });
+            }
+            WindowEvent::MouseButton(button, Action::Release, _) => {
+                let Some(press) = self.press.filter(|press| press.button == button) else {
+                    return gestures;
+                };
+                self.press = None;
+                if press.dragging {
+                    self.last_press = None;
+                    gestures.push(Gesture::DragEnd {
+                        button,
+                        pos: self.cursor_pos,
+                    });
+                } else if press.long_pressed {
+                    self.last_press = None;
+                } else {
+                    self.last_press = Some(press);
+                    gestures.push(Gesture::Click {
+                        button,
+                        count: press.count,
+                        pos: press.pos,
+                    });
+                }
+            }
+            // The release will not be delivered, so the press is abandoned
+            WindowEvent::Focus(false) => {
+                if let Some(press) = self.press.take() {
+                    if press.dragging {
+                        gestures.push(Gesture::DragEnd {
+                            button: press.button,
+                            pos: self.cursor_pos,
+                        });
+                    }
+                }
+                self.last_press = None;
+            }
+            _ => {}
+        }
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const LEFT: MouseButton = MouseButtonLeft;
+
+    fn press(time: f64) -> (f64, WindowEvent) {
+        let event = WindowEvent::MouseButton(LEFT, Action::Press, Modifiers::empty());
+        (time, event)
+    }
+
+    fn release(time: f64) -> (f64, WindowEvent) {
+        let event = WindowEvent::MouseButton(LEFT, Action::Release, Modifiers::empty());
+        (time, event)
+    }
+
+    fn move_to(time: f64, x: f64, y: f64) -> (f64, WindowEvent) {
+        (time, WindowEvent::CursorPos(x, y))
+    }
+
+    fn click(count: u32, pos: (f64, f64)) -> Vec<Gesture> {
+        vec![Gesture::Click {
+            button: LEFT,
+            count,
+            pos,
+        }]
+    }
+
+    #[test]
+    fn multi_click() {
+        let mut gestures = GestureRecognizer::new(GestureConfig::default());
+        gestures.handle_event(&move_to(0.0, 10.0, 10.0));
+        assert!(gestures.handle_event(&press(1.0)).is_empty());
+        assert_eq!(click(1, (10.0, 10.0)), gestures.handle_event(&release(1.1)));
+        gestures.handle_event(&press(1.3));
+        assert_eq!(
+            click(2, (10.0, 10.0)),
+            gestures.handle_event(&release(1.35))
+        );
+        gestures.handle_event(&move_to(1.4, 12.0, 11.0));
+        gestures.handle_event(&press(1.6));
+        assert_eq!(
+            click(3, (12.0, 11.0)),
+            gestures.handle_event(&release(1.65))
+        );
+
+        // Too slow
+        gestures.handle_event(&press(2.5));
+        assert_eq!(
+            click(1, (12.0, 11.0)),
+            gestures.handle_event(&release(2.55))
+        );
+
+        // Too far
+        gestures.handle_event(&move_to(2.6, 30.0, 11.0));
+        gestures.handle_event(&press(2.7));
+        assert_eq!(
+            click(1, (30.0, 11.0)),
+            gestures.handle_event(&release(2.75))
+        );
+    }
+
+    #[test]
+    fn drag() {
+        let mut gestures = GestureRecognizer::new(GestureConfig::default());
+        gestures.handle_event(&move_to(0.0, 10.0, 10.0));
+        gestures.handle_event(&press(1.0));
+        assert!(gestures.handle_event(&move_to(1.1, 12.0, 10.0)).is_empty());
+        assert_eq!(
+            vec![
+                Gesture::DragStart {
+                    button: LEFT,
+                    pos: (10.0, 10.0)
+                },
+                Gesture::DragMove {
+                    button: LEFT,
+                    pos: (16.0, 10.0),
+                    delta: (6.0, 0.0)
+                },
+            ],
+            gestures.handle_event(&move_to(1.2, 16.0, 10.0))
+        );
+        assert!(gestures.dragging());
+        assert_eq!(
+            vec![Gesture::DragMove {
+                button: LEFT,
+                pos: (16.0, 13.0),
+                delta: (0.0, 3.0)
+            }],
+            gestures.handle_event(&move_to(1.3, 16.0, 13.0))
+        );
+        assert_eq!(
+            vec![Gesture::DragEnd {
+                button: LEFT,
+                pos: (16.0, 13.0)
+            }],
+            gestures.handle_event(&release(1.4))
+        );
+
+        // A drag does not count towards a multi-click
+        gestures.handle_event(&press(1.5));
+        assert_eq!(
+            click(1, (16.0, 13.0)),
+            gestures.handle_event(&release(1.55))
+        );
+    }
+
+    #[test]
+    fn long_press() {
+        let mut gestures = GestureRecognizer::new(GestureConfig::default());
+        gestures.handle_event(&press(1.0));
+        assert_eq!(None, gestures.update(1.5));
+        let long_press = Gesture::LongPress {
+            button: LEFT,
+            pos: (0.0, 0.0),
+        };
+        assert_eq!(Some(long_press), gestures.update(1.6));
+        assert_eq!(None, gestures.update(1.7));
+        assert!(gestures.handle_event(&release(2.0)).is_empty());
+
+        // Detected on the next event as well
+        gestures.handle_event(&press(3.0));
+        assert_eq!(vec![long_press], gestures.handle_event(&release(4.0)));
+    }
+
+    #[test]
+    fn focus_lost() {
+        let mut gestures = GestureRecognizer::new(GestureConfig::default());
+        gestures.handle_event(&press(1.0));
+        gestures.handle_event(&move_to(1.1, 20.0, 0.0));
+        assert_eq!(
+            vec![Gesture::DragEnd {
+                button: LEFT,
+                pos: (20.0, 0.0)
+            }],
+            gestures.handle_event(&(1.2, WindowEvent::Focus(false)))
+        );
+        assert!(!gestures.dragging());
+        assert!(gestures.handle_event(&release(1.3)).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use glfw3_sys as sys;
+
+/// A 2D image with pixels arranged left-to-right, top-to-bottom.
+///
+/// The default pixel type is 8-bit RGBA, which is the format GLFW expects for
+/// window icons and cursors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image<P = [u8; 4]> {
+    width: u32,
+    height: u32,
+    pixels: Vec<P>,
+}
+
+impl<P> Image<P> {
+    /// Returns `None` if `pixels.len()` is not `width * height`.
+    pub fn new(width: u32, height: u32, pixels: Vec<P>) -> Option<Image<P>> {
+        if pixels.len() as u64 == width as u64 * height as u64 {
+            Some(Image {
+                width,
+                height,
+                pixels,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[P] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [P] {
+        &mut self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<P> {
+        self.pixels
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Option<&P> {
+        if x < self.width && y < self.height {
+            self.pixels
+                .get(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Image {
+    /// Borrows the pixels for the duration of a GLFW call that copies them.
+    pub(crate) fn as_glfw_image(&self) -> sys::GLFWimage {
+        sys::GLFWimage {
+            width: self.width as i32,
+            height: self.height as i32,
+            pixels: self.pixels.as_ptr() as *mut u8,
+        }
+    }
+}
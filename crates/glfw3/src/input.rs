@@ -0,0 +1,332 @@
+use core::ffi::CStr;
+use std::collections::BTreeMap;
+
+use glfw3_sys::{self as sys, GLFW_FALSE, GLFW_TRUE};
+
+use crate::{Action, Error, Glfw, Key, MouseButton, Scancode, Window, GLFW_NOT_INITIALIZED};
+
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CursorMode {
+    Normal = sys::GLFW_CURSOR_NORMAL,
+    Hidden = sys::GLFW_CURSOR_HIDDEN,
+    /// Hides and locks the cursor, for unbounded movement such as camera controls.
+    Disabled = sys::GLFW_CURSOR_DISABLED,
+    /// Confines the visible cursor to the content area of the window.
+    Captured = sys::GLFW_CURSOR_CAPTURED,
+}
+
+impl TryFrom<i32> for CursorMode {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            sys::GLFW_CURSOR_NORMAL => Ok(CursorMode::Normal),
+            sys::GLFW_CURSOR_HIDDEN => Ok(CursorMode::Hidden),
+            sys::GLFW_CURSOR_DISABLED => Ok(CursorMode::Disabled),
+            sys::GLFW_CURSOR_CAPTURED => Ok(CursorMode::Captured),
+            _ => Err(value),
+        }
+    }
+}
+
+/// A [`Key`] other than [`Key::Unknown`], which has no state that can be polled.
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct KnownKey(Key);
+
+impl KnownKey {
+    pub fn key(self) -> Key {
+        self.0
+    }
+}
+
+impl TryFrom<Key> for KnownKey {
+    type Error = Key;
+
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        if Key::Unknown == key {
+            Err(key)
+        } else {
+            Ok(KnownKey(key))
+        }
+    }
+}
+
+impl From<KnownKey> for Key {
+    fn from(key: KnownKey) -> Self {
+        key.0
+    }
+}
+
+unsafe fn key_name(key: i32, scancode: i32) -> Option<String> {
+    let name_ptr = sys::glfwGetKeyName(key, scancode);
+    if let Err(err) = Glfw::get_error() {
+        log::debug!("glfwGetKeyName failed: {:?}", err);
+    }
+    if name_ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(name_ptr).to_string_lossy().into_owned())
+    }
+}
+
+impl Key {
+    /// Returns `None` for [`Key::Unknown`].
+    pub fn known(self) -> Option<KnownKey> {
+        KnownKey::try_from(self).ok()
+    }
+
+    /// Returns `None` if the key does not exist on the keyboard or GLFW is not initialized.
+    #[doc(alias = "glfwGetKeyScancode")]
+    pub fn scancode(self) -> Option<Scancode> {
+        if Key::Unknown == self {
+            return None;
+        }
+        unsafe {
+            let scancode = sys::glfwGetKeyScancode(self as i32);
+            if let Err(err) = Glfw::get_error() {
+                log::debug!("glfwGetKeyScancode failed: {:?}", err);
+            }
+            if -1 == scancode {
+                None
+            } else {
+                Some(Scancode(scancode))
+            }
+        }
+    }
+
+    /// The printable label of the key in the current keyboard layout, for
+    /// example `"z"` for [`Key::W`] on AZERTY.
+    ///
+    /// Returns `None` for keys that are not printable, such as [`Key::Escape`].
+    /// Requires `&Glfw` because key names may only be queried on the main thread.
+    #[doc(alias = "glfwGetKeyName")]
+    pub fn localized_name(self, _glfw: &Glfw) -> Option<String> {
+        match self {
+            Key::Unknown => None,
+            key => unsafe { key_name(key as i32, 0) },
+        }
+    }
+}
+
+impl Scancode {
+    /// The printable label of the key in the current keyboard layout.
+    #[doc(alias = "glfwGetKeyName")]
+    pub fn name(self, _glfw: &Glfw) -> Option<String> {
+        unsafe { key_name(sys::GLFW_KEY_UNKNOWN, self.0) }
+    }
+}
+
+/// The labels of the printable keys in the keyboard layout at the time of the snapshot.
+///
+/// GLFW does not report layout changes, so take a new snapshot when
+/// showing key bindings to the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    labels: BTreeMap<Key, String>,
+}
+
+impl KeyboardLayout {
+    pub fn label(&self, key: Key) -> Option<&str> {
+        self.labels.get(&key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        self.labels
+            .iter()
+            .map(|(&key, label)| (key, label.as_str()))
+    }
+}
+
+impl Glfw {
+    pub fn keyboard_layout(&self) -> KeyboardLayout {
+        let labels = Key::all()
+            .filter_map(|key| key.localized_name(self).map(|label| (key, label)))
+            .collect();
+        KeyboardLayout { labels }
+    }
+}
+
+#[derive(Debug)]
+pub enum RawMouseMotionError {
+    /// See [`Glfw::raw_mouse_motion_supported`].
+    Unsupported,
+    Glfw(Error),
+}
+
+/// Restores the previous cursor mode of the window when dropped.
+///
+/// Created by [`Window::grab_cursor`].
+pub struct CursorGrab<'a> {
+    window: &'a Window,
+    previous: CursorMode,
+}
+
+impl CursorGrab<'_> {
+    /// The mode that is restored on drop.
+    pub fn previous(&self) -> CursorMode {
+        self.previous
+    }
+}
+
+impl Drop for CursorGrab<'_> {
+    fn drop(&mut self) {
+        if let Err(err) = self.window.set_cursor_mode(self.previous) {
+            log::warn!("failed to restore cursor mode: {:?}", err);
+        }
+    }
+}
+
+impl Glfw {
+    #[doc(alias = "glfwRawMouseMotionSupported")]
+    pub fn raw_mouse_motion_supported(&self) -> bool {
+        unsafe {
+            let supported = sys::glfwRawMouseMotionSupported();
+            Glfw::get_error().expect(GLFW_NOT_INITIALIZED);
+            GLFW_TRUE == supported
+        }
+    }
+}
+
+impl Window {
+    fn input_mode(&self, mode: i32) -> i32 {
+        unsafe {
+            let value = sys::glfwGetInputMode(self.window_ptr, mode);
+            if let Err(err) = Glfw::get_error() {
+                log::debug!("glfwGetInputMode failed: {:?}", err);
+            }
+            value
+        }
+    }
+
+    fn set_input_mode(&self, mode: i32, value: i32) -> Result<(), Error> {
+        unsafe {
+            sys::glfwSetInputMode(self.window_ptr, mode, value);
+            Glfw::get_error()
+        }
+    }
+
+    fn set_input_mode_flag(&self, mode: i32, value: bool) -> Result<(), Error> {
+        let value = if value { GLFW_TRUE } else { GLFW_FALSE };
+        self.set_input_mode(mode, value)
+    }
+
+    /// The last state reported for `key`, either [`Action::Press`] or [`Action::Release`].
+    ///
+    /// Use [`Key::known`] to get a [`KnownKey`].
+    #[doc(alias = "glfwGetKey")]
+    pub fn key(&self, key: KnownKey) -> Action {
+        unsafe {
+            let action = sys::glfwGetKey(self.window_ptr, key.0 as i32);
+            if let Err(err) = Glfw::get_error() {
+                log::debug!("glfwGetKey failed: {:?}", err);
+            }
+            Action::try_from(action).unwrap_or(Action::Release)
+        }
+    }
+
+    /// The last state reported for `button`, either [`Action::Press`] or [`Action::Release`].
+    #[doc(alias = "glfwGetMouseButton")]
+    pub fn mouse_button(&self, button: MouseButton) -> Action {
+        unsafe {
+            let action = sys::glfwGetMouseButton(self.window_ptr, button as i32);
+            if let Err(err) = Glfw::get_error() {
+                log::debug!("glfwGetMouseButton failed: {:?}", err);
+            }
+            Action::try_from(action).unwrap_or(Action::Release)
+        }
+    }
+
+    /// The cursor position in screen coordinates, relative to the top-left
+    /// corner of the content area.
+    #[doc(alias = "glfwGetCursorPos")]
+    pub fn cursor_pos(&self) -> (f64, f64) {
+        let mut xpos = 0.0;
+        let mut ypos = 0.0;
+        unsafe {
+            sys::glfwGetCursorPos(self.window_ptr, &mut xpos, &mut ypos);
+            if let Err(err) = Glfw::get_error() {
+                log::debug!("glfwGetCursorPos failed: {:?}", err);
+            }
+        }
+        (xpos, ypos)
+    }
+
+    /// Fails on Wayland, which does not allow moving the cursor.
+    #[doc(alias = "glfwSetCursorPos")]
+    pub fn set_cursor_pos(&self, xpos: f64, ypos: f64) -> Result<(), Error> {
+        crate::callbacks::reset_mouse_motion(self.window_ptr);
+        unsafe {
+            sys::glfwSetCursorPos(self.window_ptr, xpos, ypos);
+            Glfw::get_error()
+        }
+    }
+
+    #[doc(alias = "glfwGetInputMode")]
+    pub fn cursor_mode(&self) -> CursorMode {
+        let mode = self.input_mode(sys::GLFW_CURSOR);
+        CursorMode::try_from(mode).unwrap_or(CursorMode::Normal)
+    }
+
+    #[doc(alias = "glfwSetInputMode")]
+    pub fn set_cursor_mode(&self, mode: CursorMode) -> Result<(), Error> {
+        crate::callbacks::reset_mouse_motion(self.window_ptr);
+        self.set_input_mode(sys::GLFW_CURSOR, mode as i32)
+    }
+
+    /// Sets the cursor mode until the returned guard is dropped.
+    pub fn grab_cursor(&self, mode: CursorMode) -> Result<CursorGrab<'_>, Error> {
+        let previous = self.cursor_mode();
+        self.set_cursor_mode(mode)?;
+        Ok(CursorGrab {
+            window: self,
+            previous,
+        })
+    }
+
+    pub fn sticky_keys(&self) -> bool {
+        GLFW_TRUE == self.input_mode(sys::GLFW_STICKY_KEYS)
+    }
+
+    /// Keeps a key reported as pressed by [`Window::key`] until it has been polled,
+    /// even if it was released in between.
+    pub fn set_sticky_keys(&self, value: bool) -> Result<(), Error> {
+        self.set_input_mode_flag(sys::GLFW_STICKY_KEYS, value)
+    }
+
+    pub fn sticky_mouse_buttons(&self) -> bool {
+        GLFW_TRUE == self.input_mode(sys::GLFW_STICKY_MOUSE_BUTTONS)
+    }
+
+    pub fn set_sticky_mouse_buttons(&self, value: bool) -> Result<(), Error> {
+        self.set_input_mode_flag(sys::GLFW_STICKY_MOUSE_BUTTONS, value)
+    }
+
+    pub fn lock_key_mods(&self) -> bool {
+        GLFW_TRUE == self.input_mode(sys::GLFW_LOCK_KEY_MODS)
+    }
+
+    /// Includes [`Modifiers::CAPS_LOCK`](crate::Modifiers::CAPS_LOCK) and
+    /// [`Modifiers::NUM_LOCK`](crate::Modifiers::NUM_LOCK) in input events.
+    pub fn set_lock_key_mods(&self, value: bool) -> Result<(), Error> {
+        self.set_input_mode_flag(sys::GLFW_LOCK_KEY_MODS, value)
+    }
+
+    pub fn raw_mouse_motion(&self) -> bool {
+        GLFW_TRUE == self.input_mode(sys::GLFW_RAW_MOUSE_MOTION)
+    }
+
+    /// Raw motion only applies while the cursor is [`CursorMode::Disabled`].
+    pub fn set_raw_mouse_motion(&self, value: bool) -> Result<(), RawMouseMotionError> {
+        unsafe {
+            if value && GLFW_TRUE != sys::glfwRawMouseMotionSupported() {
+                return Err(RawMouseMotionError::Unsupported);
+            }
+        }
+        crate::callbacks::reset_mouse_motion(self.window_ptr);
+        let value = if value { GLFW_TRUE } else { GLFW_FALSE };
+        self.set_input_mode(sys::GLFW_RAW_MOUSE_MOTION, value)
+            .map_err(RawMouseMotionError::Glfw)
+    }
+}
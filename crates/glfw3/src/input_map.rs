@@ -0,0 +1,523 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    Action, AxisRange, GamepadAxis, GamepadButton, GamepadState, Key, Modifiers, MouseButton,
+    Scancode, WindowEvent,
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A single input. Keys and mouse buttons only count while the given
+/// modifiers were held when they were pressed, so `Key(S, CONTROL)` does not
+/// fire on a plain S while `Key(S, empty)` fires on both.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+    Key(Key, Modifiers),
+    /// A physical key, independent of the keyboard layout.
+    Scancode(Scancode, Modifiers),
+    MouseButton(MouseButton, Modifiers),
+    /// Counts for the frame in which the wheel moved.
+    Scroll(ScrollDirection),
+    GamepadButton(GamepadButton),
+    GamepadAxis(GamepadAxis, AxisRange),
+}
+
+/// How the inputs of an action combine into its value.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionBinding {
+    Single(Binding),
+    /// Two inputs forming an axis, such as A and D.
+    Axis {
+        negative: Binding,
+        positive: Binding,
+    },
+    /// Four inputs forming a 2D axis, such as WASD. Like gamepad sticks, +Y
+    /// points down.
+    Axis2d {
+        up: Binding,
+        down: Binding,
+        left: Binding,
+        right: Binding,
+    },
+}
+
+/// The bindings of every action, by name.
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingSet {
+    pub actions: BTreeMap<String, Vec<ActionBinding>>,
+}
+
+impl BindingSet {
+    pub fn bind(&mut self, action: &str, binding: ActionBinding) {
+        self.actions.entry(action.into()).or_default().push(binding);
+    }
+}
+
+/// The value an action must reach to count as pressed.
+const PRESS_THRESHOLD: f32 = 0.5;
+
+/// Maps named actions onto keys, mouse buttons, the scroll wheel and a gamepad.
+///
+/// Feed it window events with [`InputMap::handle_event`] and the gamepad of
+/// the player with [`InputMap::set_gamepad_state`], query the actions, then
+/// call [`InputMap::next_frame`].
+#[derive(Clone, Debug, Default)]
+pub struct InputMap {
+    bindings: BindingSet,
+    keys: HashMap<Key, Modifiers>,
+    scancodes: HashMap<Scancode, Modifiers>,
+    mouse_buttons: HashMap<MouseButton, Modifiers>,
+    /// Presses since the last frame, which catch taps shorter than a frame.
+    presses: Vec<Binding>,
+    scroll: (f64, f64),
+    gamepad: Option<GamepadState>,
+    previous_gamepad: Option<GamepadState>,
+    was_pressed: HashSet<String>,
+}
+
+fn axis_part(value: f32, range: AxisRange) -> f32 {
+    match range {
+        AxisRange::Full => value,
+        AxisRange::Positive => value.max(0.0),
+        AxisRange::Negative => (-value).max(0.0),
+    }
+}
+
+impl InputMap {
+    pub fn new(bindings: BindingSet) -> InputMap {
+        InputMap {
+            bindings,
+            ..Default::default()
+        }
+    }
+
+    pub fn bindings(&self) -> &BindingSet {
+        &self.bindings
+    }
+
+    pub fn set_bindings(&mut self, bindings: BindingSet) {
+        self.bindings = bindings;
+    }
+
+    /// Replaces the bindings of one action.
+    pub fn rebind(&mut self, action: &str, bindings: Vec<ActionBinding>) {
+        self.bindings.actions.insert(action.into(), bindings);
+    }
+
+    pub fn unbind(&mut self, action: &str) -> Option<Vec<ActionBinding>> {
+        self.bindings.actions.remove(action)
+    }
+
+    /// The most recent key, mouse button or scroll input since the last frame,
+    /// for rebinding screens.
+    pub fn last_press(&self) -> Option<Binding> {
+        self.presses.last().copied()
+    }
+
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        match *event {
+            // Unknown keys are only tracked by scancode, as they would all
+            // share the same `Key`
+            WindowEvent::Key(key, scancode, action, mods) => match action {
+                Action::Press => {
+                    self.scancodes.insert(scancode, mods);
+                    self.presses.push(Binding::Scancode(scancode, mods));
+                    if Key::Unknown != key {
+                        self.keys.insert(key, mods);
+                        self.presses.push(Binding::Key(key, mods));
+                    }
+                }
+                Action::Release => {
+                    if Key::Unknown != key {
+                        self.keys.remove(&key);
+                    }
+                    self.scancodes.remove(&scancode);
+                }
+                Action::Repeat => {}
+            },
+            WindowEvent::MouseButton(button, action, mods) => match action {
+                Action::Press => {
+                    self.mouse_buttons.insert(button, mods);
+                    self.presses.push(Binding::MouseButton(button, mods));
+                }
+                Action::Release => {
+                    self.mouse_buttons.remove(&button);
+                }
+                Action::Repeat => {}
+            },
+            WindowEvent::Scroll(x, y) => {
+                self.scroll.0 += x;
+                self.scroll.1 += y;
+                let directions = [
+                    (y > 0.0, ScrollDirection::Up),
+                    (y < 0.0, ScrollDirection::Down),
+                    (x < 0.0, ScrollDirection::Left),
+                    (x > 0.0, ScrollDirection::Right),
+                ];
+                for (_, direction) in directions.iter().filter(|(moved, _)| *moved) {
+                    self.presses.push(Binding::Scroll(*direction));
+                }
+            }
+            // Releases are not delivered to unfocused windows
+            WindowEvent::Focus(false) => {
+                self.keys.clear();
+                self.scancodes.clear();
+                self.mouse_buttons.clear();
+            }
+            _ => {}
+        }
+    }
+
+    /// The gamepad of the player, or `None` if it is disconnected.
+    pub fn set_gamepad_state(&mut self, state: Option<GamepadState>) {
+        self.gamepad = state;
+    }
+
+    /// Starts a new frame, after which `just_pressed` and `just_released`
+    /// compare against the current state.
+    pub fn next_frame(&mut self) {
+        let pressed = self
+            .bindings
+            .actions
+            .keys()
+            .filter(|action| self.action_pressed(action))
+            .cloned()
+            .collect();
+        self.was_pressed = pressed;
+        self.presses.clear();
+        self.scroll = (0.0, 0.0);
+        self.previous_gamepad = self.gamepad;
+    }
+
+    fn held(held: Option<&Modifiers>, mods: Modifiers) -> f32 {
+        match held {
+            Some(held) if held.contains(mods) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// The value of a binding in `0.0..=1.0`, or `-1.0..=1.0` for full axes.
+    pub fn binding_value(&self, binding: &Binding) -> f32 {
+        match *binding {
+            Binding::Key(key, mods) => InputMap::held(self.keys.get(&key), mods),
+            Binding::Scancode(scancode, mods) => {
+                InputMap::held(self.scancodes.get(&scancode), mods)
+            }
+            Binding::MouseButton(button, mods) => {
+                InputMap::held(self.mouse_buttons.get(&button), mods)
+            }
+            Binding::Scroll(direction) => {
+                let (x, y) = self.scroll;
+                let amount = match direction {
+                    ScrollDirection::Up => y,
+                    ScrollDirection::Down => -y,
+                    ScrollDirection::Left => -x,
+                    ScrollDirection::Right => x,
+                };
+                amount.clamp(0.0, 1.0) as f32
+            }
+            Binding::GamepadButton(button) => match self.gamepad {
+                Some(state) if state.button(button) => 1.0,
+                _ => 0.0,
+            },
+            Binding::GamepadAxis(axis, range) => self
+                .gamepad
+                .map_or(0.0, |state| axis_part(state.axis(axis), range)),
+        }
+    }
+
+    fn binding_pressed_since_last_frame(&self, binding: &Binding) -> bool {
+        let matches = |press: &Binding| match (*press, *binding) {
+            (Binding::Key(a, held), Binding::Key(b, mods)) => a == b && held.contains(mods),
+            (Binding::Scancode(a, held), Binding::Scancode(b, mods)) => {
+                a == b && held.contains(mods)
+            }
+            (Binding::MouseButton(a, held), Binding::MouseButton(b, mods)) => {
+                a == b && held.contains(mods)
+            }
+            (press, binding) => press == binding,
+        };
+        match *binding {
+            Binding::GamepadButton(button) => {
+                let now = self.gamepad.is_some_and(|state| state.button(button));
+                let before = self
+                    .previous_gamepad
+                    .is_some_and(|state| state.button(button));
+                now && !before
+            }
+            _ => self.presses.iter().any(matches),
+        }
+    }
+
+    fn action_binding_value(&self, binding: &ActionBinding) -> (f32, f32) {
+        match binding {
+            ActionBinding::Single(binding) => (self.binding_value(binding), 0.0),
+            ActionBinding::Axis { negative, positive } => {
+                let value = self.binding_value(positive) - self.binding_value(negative);
+                (value, 0.0)
+            }
+            ActionBinding::Axis2d {
+                up,
+                down,
+                left,
+                right,
+            } => {
+                let x = self.binding_value(right) - self.binding_value(left);
+                let y = self.binding_value(down) - self.binding_value(up);
+                (x, y)
+            }
+        }
+    }
+
+    /// The combined 2D value of an action, clamped to the unit circle so
+    /// that diagonals are not faster than straight movement.
+    pub fn value2d(&self, action: &str) -> (f32, f32) {
+        let Some(bindings) = self.bindings.actions.get(action) else {
+            return (0.0, 0.0);
+        };
+        let (x, y) = bindings
+            .iter()
+            .map(|binding| self.action_binding_value(binding))
+            .fold((0.0, 0.0), |(x, y), (dx, dy)| (x + dx, y + dy));
+        let length = x.hypot(y);
+        if length > 1.0 {
+            (x / length, y / length)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// The value of a 1D action, or the magnitude of an action with an
+    /// [`ActionBinding::Axis2d`] binding.
+    pub fn value(&self, action: &str) -> f32 {
+        let is_2d = self.bindings.actions.get(action).is_some_and(|bindings| {
+            bindings
+                .iter()
+                .any(|binding| matches!(binding, ActionBinding::Axis2d { .. }))
+        });
+        let (x, y) = self.value2d(action);
+        if is_2d {
+            x.hypot(y)
+        } else {
+            x
+        }
+    }
+
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.value(action).abs() >= PRESS_THRESHOLD
+    }
+
+    /// Whether the action was pressed since the last frame, including presses
+    /// that were released again before this frame.
+    pub fn just_pressed(&self, action: &str) -> bool {
+        if self.was_pressed.contains(action) {
+            return false;
+        }
+        self.action_pressed(action)
+            || self.bindings.actions.get(action).is_some_and(|bindings| {
+                bindings.iter().any(|binding| match binding {
+                    ActionBinding::Single(binding) => {
+                        self.binding_pressed_since_last_frame(binding)
+                    }
+                    ActionBinding::Axis { negative, positive } => [negative, positive]
+                        .into_iter()
+                        .any(|binding| self.binding_pressed_since_last_frame(binding)),
+                    ActionBinding::Axis2d {
+                        up,
+                        down,
+                        left,
+                        right,
+                    } => [up, down, left, right]
+                        .into_iter()
+                        .any(|binding| self.binding_pressed_since_last_frame(binding)),
+                })
+            })
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.was_pressed.contains(action) && !self.action_pressed(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn key(key: Key, action: Action, mods: Modifiers) -> WindowEvent {
+        WindowEvent::Key(key, Scancode(key as i32), action, mods)
+    }
+
+    fn wasd() -> ActionBinding {
+        let key = |key| Binding::Key(key, Modifiers::empty());
+        ActionBinding::Axis2d {
+            up: key(Key::W),
+            down: key(Key::S),
+            left: key(Key::A),
+            right: key(Key::D),
+        }
+    }
+
+    #[test]
+    fn pressed_and_released() {
+        let mut bindings = BindingSet::default();
+        bindings.bind(
+            "jump",
+            ActionBinding::Single(Binding::Key(Key::Space, Modifiers::empty())),
+        );
+        let mut input = InputMap::new(bindings);
+
+        input.handle_event(&key(Key::Space, Action::Press, Modifiers::empty()));
+        assert!(input.action_pressed("jump"));
+        assert!(input.just_pressed("jump"));
+        input.next_frame();
+        assert!(input.action_pressed("jump"));
+        assert!(!input.just_pressed("jump"));
+
+        input.handle_event(&key(Key::Space, Action::Release, Modifiers::empty()));
+        assert!(input.just_released("jump"));
+        input.next_frame();
+        assert!(!input.just_released("jump"));
+
+        // A tap within a single frame
+        input.handle_event(&key(Key::Space, Action::Press, Modifiers::empty()));
+        input.handle_event(&key(Key::Space, Action::Release, Modifiers::empty()));
+        assert!(!input.action_pressed("jump"));
+        assert!(input.just_pressed("jump"));
+    }
+
+    #[test]
+    fn composite_axis() {
+        let mut bindings = BindingSet::default();
+        bindings.bind("move", wasd());
+        let mut input = InputMap::new(bindings);
+
+        input.handle_event(&key(Key::A, Action::Press, Modifiers::empty()));
+        assert_eq!((-1.0, 0.0), input.value2d("move"));
+        assert_eq!(1.0, input.value("move"));
+        input.handle_event(&key(Key::A, Action::Release, Modifiers::empty()));
+
+        input.handle_event(&key(Key::D, Action::Press, Modifiers::empty()));
+        assert_eq!((1.0, 0.0), input.value2d("move"));
+        input.handle_event(&key(Key::W, Action::Press, Modifiers::empty()));
+        let (x, y) = input.value2d("move");
+        assert!((x - 0.70710677).abs() < 1e-6 && (y + 0.70710677).abs() < 1e-6);
+        assert!((input.value("move") - 1.0).abs() < 1e-6);
+
+        input.handle_event(&WindowEvent::Focus(false));
+        assert_eq!((0.0, 0.0), input.value2d("move"));
+        input.next_frame();
+
+        // A tap within a single frame
+        input.handle_event(&key(Key::S, Action::Press, Modifiers::empty()));
+        input.handle_event(&key(Key::S, Action::Release, Modifiers::empty()));
+        assert!(!input.action_pressed("move"));
+        assert!(input.just_pressed("move"));
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let mut bindings = BindingSet::default();
+        bindings.bind(
+            "extra",
+            ActionBinding::Single(Binding::Scancode(Scancode(301), Modifiers::empty())),
+        );
+        let mut input = InputMap::new(bindings);
+
+        let unknown = |scancode, action| {
+            WindowEvent::Key(Key::Unknown, Scancode(scancode), action, Modifiers::empty())
+        };
+        input.handle_event(&unknown(300, Action::Press));
+        input.handle_event(&unknown(301, Action::Press));
+        input.handle_event(&unknown(300, Action::Release));
+        assert!(input.action_pressed("extra"));
+        assert_eq!(
+            Some(Binding::Scancode(Scancode(301), Modifiers::empty())),
+            input.last_press()
+        );
+    }
+
+    #[test]
+    fn modifier_bindings() {
+        let mut bindings = BindingSet::default();
+        bindings.bind(
+            "save",
+            ActionBinding::Single(Binding::Key(Key::S, Modifiers::CONTROL)),
+        );
+        bindings.bind(
+            "back",
+            ActionBinding::Single(Binding::Key(Key::S, Modifiers::empty())),
+        );
+        let mut input = InputMap::new(bindings);
+
+        input.handle_event(&key(Key::S, Action::Press, Modifiers::empty()));
+        assert!(!input.action_pressed("save"));
+        assert!(input.action_pressed("back"));
+        input.handle_event(&key(Key::S, Action::Release, Modifiers::empty()));
+
+        let mods = Modifiers::CONTROL | Modifiers::NUM_LOCK;
+        input.handle_event(&key(Key::S, Action::Press, mods));
+        assert!(input.action_pressed("save"));
+        assert!(input.just_pressed("save"));
+    }
+
+    #[test]
+    fn gamepad_and_rebinding() {
+        let mut bindings = BindingSet::default();
+        bindings.bind(
+            "move_x",
+            ActionBinding::Single(Binding::GamepadAxis(GamepadAxis::LeftX, AxisRange::Full)),
+        );
+        bindings.bind(
+            "fire",
+            ActionBinding::Single(Binding::GamepadButton(GamepadButton::A)),
+        );
+        let mut input = InputMap::new(bindings);
+
+        let mut state = GamepadState::default();
+        state.set_axis(GamepadAxis::LeftX, -0.25);
+        state.set_button(GamepadButton::A, true);
+        input.set_gamepad_state(Some(state));
+        assert_eq!(-0.25, input.value("move_x"));
+        assert!(input.just_pressed("fire"));
+        input.next_frame();
+        assert!(!input.just_pressed("fire"));
+
+        input.set_gamepad_state(None);
+        input.next_frame();
+        input.handle_event(&WindowEvent::Scroll(0.0, -1.0));
+        assert_eq!(
+            Some(Binding::Scroll(ScrollDirection::Down)),
+            input.last_press()
+        );
+        input.rebind(
+            "fire",
+            vec![ActionBinding::Single(input.last_press().unwrap())],
+        );
+        assert!(input.just_pressed("fire"));
+        input.next_frame();
+        assert!(!input.action_pressed("fire"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_bindings() {
+        let mut bindings = BindingSet::default();
+        bindings.bind("move", wasd());
+        bindings.bind(
+            "save",
+            ActionBinding::Single(Binding::Key(Key::S, Modifiers::CONTROL | Modifiers::SHIFT)),
+        );
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert_eq!(bindings, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Action, Key, Modifiers, MouseButton, Scancode, WindowEvent};
+
+/// Buttons that are held, with those that changed during the current frame.
+#[derive(Clone, Debug)]
+struct ButtonSet<T> {
+    /// The time of the press that is being held.
+    held: HashMap<T, f64>,
+    pressed: HashSet<T>,
+    released: HashSet<T>,
+}
+
+impl<T> Default for ButtonSet<T> {
+    fn default() -> Self {
+        ButtonSet {
+            held: HashMap::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + core::hash::Hash> ButtonSet<T> {
+    fn update(&mut self, button: T, action: Action, time: f64) {
+        match action {
+            Action::Press => {
+                self.held.insert(button, time);
+                self.pressed.insert(button);
+            }
+            Action::Release => {
+                if self.held.remove(&button).is_some() {
+                    self.released.insert(button);
+                }
+            }
+            Action::Repeat => {}
+        }
+    }
+
+    fn release_all(&mut self) {
+        self.released
+            .extend(self.held.drain().map(|(button, _)| button));
+    }
+
+    fn next_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+}
+
+fn modifier_of(key: Key) -> Option<(Modifiers, Key)> {
+    match key {
+        Key::LeftShift => Some((Modifiers::SHIFT, Key::RightShift)),
+        Key::RightShift => Some((Modifiers::SHIFT, Key::LeftShift)),
+        Key::LeftControl => Some((Modifiers::CONTROL, Key::RightControl)),
+        Key::RightControl => Some((Modifiers::CONTROL, Key::LeftControl)),
+        Key::LeftAlt => Some((Modifiers::ALT, Key::RightAlt)),
+        Key::RightAlt => Some((Modifiers::ALT, Key::LeftAlt)),
+        Key::LeftSuper => Some((Modifiers::SUPER, Key::RightSuper)),
+        Key::RightSuper => Some((Modifiers::SUPER, Key::LeftSuper)),
+        _ => None,
+    }
+}
+
+/// The keyboard and mouse state of a window, built from its events.
+///
+/// Feed it every event from [`Glfw::poll_events`](crate::Glfw::poll_events)
+/// with [`InputState::handle_event`], read it, then call
+/// [`InputState::next_frame`]. The "just" queries and the accumulated
+/// cursor delta and scroll cover the events since the last frame.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    keys: ButtonSet<Key>,
+    scancodes: ButtonSet<Scancode>,
+    mouse_buttons: ButtonSet<MouseButton>,
+    mods: Modifiers,
+    cursor_pos: Option<(f64, f64)>,
+    cursor_delta: (f64, f64),
+    /// Set once a `MouseMotion` event arrives, after which the delta is no
+    /// longer derived from `CursorPos` events.
+    motion_events: bool,
+    scroll: (f64, f64),
+}
+
+impl InputState {
+    pub fn new() -> InputState {
+        InputState::default()
+    }
+
+    pub fn handle_event(&mut self, (time, event): &(f64, WindowEvent)) {
+        match *event {
+            WindowEvent::Key(key, scancode, action, mods) => {
+                if Key::Unknown != key {
+                    self.keys.update(key, action, *time);
+                }
+                self.scancodes.update(scancode, action, *time);
+                self.mods = mods;
+                // Platforms disagree on whether the modifier bits of a
+                // modifier key event already include the key itself
+                if let Some((modifier, other)) = modifier_of(key) {
+                    match action {
+                        Action::Press | Action::Repeat => self.mods.insert(modifier),
+                        Action::Release if !self.keys.held.contains_key(&other) => {
+                            self.mods.remove(modifier)
+                        }
+                        Action::Release => {}
+                    }
+                }
+            }
+            WindowEvent::MouseButton(button, action, mods) => {
+                self.mouse_buttons.update(button, action, *time);
+                self.mods = mods;
+            }
+            WindowEvent::CursorPos(x, y) => {
+                if let Some((last_x, last_y)) = self.cursor_pos.filter(|_| !self.motion_events) {
+                    self.cursor_delta.0 += x - last_x;
+                    self.cursor_delta.1 += y - last_y;
+                }
+                self.cursor_pos = Some((x, y));
+            }
+            WindowEvent::MouseMotion(dx, dy) => {
+                if !self.motion_events {
+                    // Discard what was derived from the cursor position
+                    self.motion_events = true;
+                    self.cursor_delta = (0.0, 0.0);
+                }
+                self.cursor_delta.0 += dx;
+                self.cursor_delta.1 += dy;
+            }
+            WindowEvent::Scroll(x, y) => {
+                self.scroll.0 += x;
+                self.scroll.1 += y;
+            }
+            // GLFW does not deliver releases to unfocused windows, so
+            // everything held is released when focus is lost
+            WindowEvent::Focus(false) => {
+                self.keys.release_all();
+                self.scancodes.release_all();
+                self.mouse_buttons.release_all();
+                self.mods &= Modifiers::CAPS_LOCK | Modifiers::NUM_LOCK;
+                self.cursor_pos = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn next_frame(&mut self) {
+        self.keys.next_frame();
+        self.scancodes.next_frame();
+        self.mouse_buttons.next_frame();
+        self.cursor_delta = (0.0, 0.0);
+        self.scroll = (0.0, 0.0);
+    }
+
+    pub fn key_pressed(&self, key: Key) -> bool {
+        self.keys.held.contains_key(&key)
+    }
+
+    /// The time of the press of a held key.
+    pub fn key_pressed_at(&self, key: Key) -> Option<f64> {
+        self.keys.held.get(&key).copied()
+    }
+
+    pub fn key_just_pressed(&self, key: Key) -> bool {
+        self.keys.pressed.contains(&key)
+    }
+
+    pub fn key_just_released(&self, key: Key) -> bool {
+        self.keys.released.contains(&key)
+    }
+
+    pub fn pressed_keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.keys.held.keys().copied()
+    }
+
+    pub fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        self.scancodes.held.contains_key(&scancode)
+    }
+
+    pub fn scancode_just_pressed(&self, scancode: Scancode) -> bool {
+        self.scancodes.pressed.contains(&scancode)
+    }
+
+    pub fn scancode_just_released(&self, scancode: Scancode) -> bool {
+        self.scancodes.released.contains(&scancode)
+    }
+
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.held.contains_key(&button)
+    }
+
+    pub fn mouse_button_pressed_at(&self, button: MouseButton) -> Option<f64> {
+        self.mouse_buttons.held.get(&button).copied()
+    }
+
+    pub fn mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.pressed.contains(&button)
+    }
+
+    pub fn mouse_button_just_released(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.released.contains(&button)
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.mods
+    }
+
+    /// `None` until the first cursor event and after focus is lost.
+    pub fn cursor_pos(&self) -> Option<(f64, f64)> {
+        self.cursor_pos
+    }
+
+    /// The accumulated [`WindowEvent::MouseMotion`] of the current frame.
+    /// Without motion events, such as for events that did not come from
+    /// GLFW, it is the difference between the `CursorPos` events instead.
+    pub fn cursor_delta(&self) -> (f64, f64) {
+        self.cursor_delta
+    }
+
+    pub fn scroll(&self) -> (f64, f64) {
+        self.scroll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn key(time: f64, key: Key, action: Action, mods: Modifiers) -> (f64, WindowEvent) {
+        (
+            time,
+            WindowEvent::Key(key, Scancode(key as i32), action, mods),
+        )
+    }
+
+    #[test]
+    fn keys() {
+        let mut input = InputState::new();
+        input.handle_event(&key(1.0, Key::W, Action::Press, Modifiers::empty()));
+        input.handle_event(&key(1.1, Key::W, Action::Repeat, Modifiers::empty()));
+        assert!(input.key_pressed(Key::W));
+        assert!(input.scancode_pressed(Scancode(Key::W as i32)));
+        assert!(input.key_just_pressed(Key::W));
+        assert_eq!(Some(1.0), input.key_pressed_at(Key::W));
+
+        input.next_frame();
+        assert!(input.key_pressed(Key::W));
+        assert!(!input.key_just_pressed(Key::W));
+
+        input.handle_event(&key(2.0, Key::W, Action::Release, Modifiers::empty()));
+        assert!(!input.key_pressed(Key::W));
+        assert!(input.key_just_released(Key::W));
+        assert!(input.scancode_just_released(Scancode(Key::W as i32)));
+    }
+
+    #[test]
+    fn modifiers() {
+        let mut input = InputState::new();
+        input.handle_event(&key(1.0, Key::LeftShift, Action::Press, Modifiers::empty()));
+        input.handle_event(&key(1.1, Key::RightShift, Action::Press, Modifiers::SHIFT));
+        assert_eq!(Modifiers::SHIFT, input.modifiers());
+        input.handle_event(&key(1.2, Key::LeftShift, Action::Release, Modifiers::SHIFT));
+        assert_eq!(Modifiers::SHIFT, input.modifiers());
+        input.handle_event(&key(
+            1.3,
+            Key::RightShift,
+            Action::Release,
+            Modifiers::SHIFT,
+        ));
+        assert_eq!(Modifiers::empty(), input.modifiers());
+    }
+
+    #[test]
+    fn mouse() {
+        let mut input = InputState::new();
+        input.handle_event(&(1.0, WindowEvent::CursorPos(10.0, 10.0)));
+        assert_eq!((0.0, 0.0), input.cursor_delta());
+        input.handle_event(&(1.1, WindowEvent::CursorPos(15.0, 8.0)));
+        input.handle_event(&(1.1, WindowEvent::MouseMotion(5.0, -2.0)));
+        input.handle_event(&(1.2, WindowEvent::CursorPos(20.0, 8.0)));
+        input.handle_event(&(1.2, WindowEvent::MouseMotion(5.0, 0.0)));
+        input.handle_event(&(1.3, WindowEvent::Scroll(0.0, 1.0)));
+        input.handle_event(&(1.4, WindowEvent::Scroll(0.0, 2.0)));
+        let press =
+            WindowEvent::MouseButton(MouseButton::Button1, Action::Press, Modifiers::empty());
+        input.handle_event(&(1.5, press));
+        assert_eq!(Some((20.0, 8.0)), input.cursor_pos());
+        assert_eq!((10.0, -2.0), input.cursor_delta());
+        assert_eq!((0.0, 3.0), input.scroll());
+        assert!(input.mouse_button_just_pressed(MouseButtonLeft));
+
+        input.next_frame();
+        assert_eq!((0.0, 0.0), input.cursor_delta());
+        assert_eq!((0.0, 0.0), input.scroll());
+        assert!(input.mouse_button_pressed(MouseButtonLeft));
+    }
+
+    #[test]
+    fn focus_lost() {
+        let mut input = InputState::new();
+        let mods = Modifiers::CONTROL | Modifiers::NUM_LOCK;
+        input.handle_event(&key(1.0, Key::LeftControl, Action::Press, mods));
+        input.handle_event(&key(1.1, Key::S, Action::Press, mods));
+        input.handle_event(&(1.1, WindowEvent::CursorPos(5.0, 5.0)));
+        input.next_frame();
+
+        input.handle_event(&(2.0, WindowEvent::Focus(false)));
+        assert!(!input.key_pressed(Key::S));
+        assert!(input.key_just_released(Key::S));
+        assert!(input.key_just_released(Key::LeftControl));
+        assert_eq!(Modifiers::NUM_LOCK, input.modifiers());
+        assert_eq!(None, input.cursor_pos());
+
+        // No jump from the position before focus was lost
+        input.handle_event(&(3.0, WindowEvent::CursorPos(500.0, 500.0)));
+        assert_eq!((0.0, 0.0), input.cursor_delta());
+    }
+
+    #[test]
+    fn cursor_pos_delta() {
+        let mut input = InputState::new();
+        input.handle_event(&(1.0, WindowEvent::CursorPos(10.0, 10.0)));
+        input.handle_event(&(1.1, WindowEvent::CursorPos(15.0, 8.0)));
+        assert_eq!((5.0, -2.0), input.cursor_delta());
+
+        // Motion events replace the deltas of the positions
+        input.handle_event(&(1.2, WindowEvent::CursorPos(17.0, 8.0)));
+        input.handle_event(&(1.2, WindowEvent::MouseMotion(2.0, 0.0)));
+        assert_eq!((2.0, 0.0), input.cursor_delta());
+        input.handle_event(&(1.3, WindowEvent::CursorPos(500.0, 500.0)));
+        assert_eq!((2.0, 0.0), input.cursor_delta());
+    }
+}
@@ -0,0 +1,333 @@
+use core::{
+    ffi::{c_char, CStr},
+    fmt, slice,
+    str::FromStr,
+};
+use std::rc::Rc;
+
+use glfw3_sys as sys;
+
+use crate::{Action, Glfw, Terminate};
+
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum JoystickId {
+    Joystick1 = sys::GLFW_JOYSTICK_1,
+    Joystick2 = sys::GLFW_JOYSTICK_2,
+    Joystick3 = sys::GLFW_JOYSTICK_3,
+    Joystick4 = sys::GLFW_JOYSTICK_4,
+    Joystick5 = sys::GLFW_JOYSTICK_5,
+    Joystick6 = sys::GLFW_JOYSTICK_6,
+    Joystick7 = sys::GLFW_JOYSTICK_7,
+    Joystick8 = sys::GLFW_JOYSTICK_8,
+    Joystick9 = sys::GLFW_JOYSTICK_9,
+    Joystick10 = sys::GLFW_JOYSTICK_10,
+    Joystick11 = sys::GLFW_JOYSTICK_11,
+    Joystick12 = sys::GLFW_JOYSTICK_12,
+    Joystick13 = sys::GLFW_JOYSTICK_13,
+    Joystick14 = sys::GLFW_JOYSTICK_14,
+    Joystick15 = sys::GLFW_JOYSTICK_15,
+    Joystick16 = sys::GLFW_JOYSTICK_16,
+}
+
+impl JoystickId {
+    pub fn all() -> impl Iterator<Item = JoystickId> {
+        (sys::GLFW_JOYSTICK_1..=sys::GLFW_JOYSTICK_LAST)
+            .map(|value| unsafe { core::mem::transmute::<i32, JoystickId>(value) })
+    }
+}
+
+impl TryFrom<i32> for JoystickId {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        if (sys::GLFW_JOYSTICK_1..=sys::GLFW_JOYSTICK_LAST).contains(&value) {
+            return Ok(unsafe { core::mem::transmute::<i32, JoystickId>(value) });
+        }
+        Err(value)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum JoystickEvent {
+    Connected(JoystickId),
+    Disconnected(JoystickId),
+}
+
+bitflags::bitflags! {
+    /// The directions a hat is pressed in. A centered hat is empty.
+    #[repr(transparent)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct HatState: u8 {
+        const UP    = sys::GLFW_HAT_UP as u8;
+        const RIGHT = sys::GLFW_HAT_RIGHT as u8;
+        const DOWN  = sys::GLFW_HAT_DOWN as u8;
+        const LEFT  = sys::GLFW_HAT_LEFT as u8;
+    }
+}
+
+/// The SDL-compatible GUID GLFW assigns to a joystick model.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct JoystickGuid([u8; 16]);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGuidError;
+
+impl fmt::Display for ParseGuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a joystick GUID must be 32 hexadecimal digits")
+    }
+}
+
+impl core::error::Error for ParseGuidError {}
+
+impl JoystickGuid {
+    pub fn from_bytes(bytes: [u8; 16]) -> JoystickGuid {
+        JoystickGuid(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    fn word(&self, index: usize) -> u16 {
+        u16::from_le_bytes([self.0[2 * index], self.0[2 * index + 1]])
+    }
+
+    /// Whether the GUID follows the bus, vendor, product and version layout,
+    /// which excludes e.g. XInput devices on Windows.
+    fn has_hardware_ids(&self) -> bool {
+        0 == self.word(3) && 0 == self.word(5)
+    }
+
+    /// The bus type, such as `0x03` for USB or `0x05` for Bluetooth.
+    pub fn bus(&self) -> u16 {
+        self.word(0)
+    }
+
+    pub fn vendor(&self) -> Option<u16> {
+        self.has_hardware_ids().then(|| self.word(2))
+    }
+
+    pub fn product(&self) -> Option<u16> {
+        self.has_hardware_ids().then(|| self.word(4))
+    }
+
+    pub fn version(&self) -> Option<u16> {
+        self.has_hardware_ids().then(|| self.word(6))
+    }
+}
+
+impl FromStr for JoystickGuid {
+    type Err = ParseGuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if 32 != s.len() || !s.is_ascii() {
+            return Err(ParseGuidError);
+        }
+        let mut bytes = [0; 16];
+        for (byte, digits) in bytes.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+            let digits = core::str::from_utf8(digits).map_err(|_| ParseGuidError)?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| ParseGuidError)?;
+        }
+        Ok(JoystickGuid(bytes))
+    }
+}
+
+impl fmt::Display for JoystickGuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JoystickGuid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for JoystickGuid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let guid = <String as serde::Deserialize>::deserialize(deserializer)?;
+        guid.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The inputs of a joystick at the time it was polled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoystickState {
+    pub axes: Vec<f32>,
+    pub buttons: Vec<Action>,
+    pub hats: Vec<HatState>,
+}
+
+/// A joystick slot. The device in the slot can be connected and disconnected
+/// at any time, so every query returns `None` while it is absent.
+pub struct Joystick {
+    id: JoystickId,
+    _terminate: Rc<Terminate>,
+}
+
+unsafe fn joystick_array<T: Copy, U>(
+    array_ptr: *const T,
+    count: i32,
+    map: impl Fn(T) -> U,
+) -> Option<Vec<U>> {
+    if array_ptr.is_null() {
+        None
+    } else {
+        let array = slice::from_raw_parts(array_ptr, count.max(0) as usize);
+        Some(array.iter().map(|&value| map(value)).collect())
+    }
+}
+
+/// Clears an error raised while polling a joystick, such as a platform
+/// error for a device that is being disconnected.
+pub(crate) fn clear_joystick_error(function: &str) {
+    if let Err(err) = Glfw::get_error() {
+        log::debug!("{} failed: {:?}", function, err);
+    }
+}
+
+pub(crate) unsafe fn joystick_string(string_ptr: *const c_char) -> Option<String> {
+    if string_ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(string_ptr).to_string_lossy().into_owned())
+    }
+}
+
+impl Joystick {
+    pub fn id(&self) -> JoystickId {
+        self.id
+    }
+
+    #[doc(alias = "glfwJoystickPresent")]
+    pub fn is_present(&self) -> bool {
+        let present = unsafe { sys::glfwJoystickPresent(self.id as i32) };
+        clear_joystick_error("glfwJoystickPresent");
+        sys::GLFW_TRUE == present
+    }
+
+    /// Axis positions in the range `-1.0..=1.0`.
+    #[doc(alias = "glfwGetJoystickAxes")]
+    pub fn axes(&self) -> Option<Vec<f32>> {
+        unsafe {
+            let mut count = 0;
+            let axes_ptr = sys::glfwGetJoystickAxes(self.id as i32, &mut count);
+            clear_joystick_error("glfwGetJoystickAxes");
+            joystick_array(axes_ptr, count, |axis| axis)
+        }
+    }
+
+    /// Includes the hats as four buttons each, unless disabled with
+    /// [`InitHint::JoystickHatButtons`](crate::InitHint::JoystickHatButtons).
+    #[doc(alias = "glfwGetJoystickButtons")]
+    pub fn buttons(&self) -> Option<Vec<Action>> {
+        unsafe {
+            let mut count = 0;
+            let buttons_ptr = sys::glfwGetJoystickButtons(self.id as i32, &mut count);
+            clear_joystick_error("glfwGetJoystickButtons");
+            joystick_array(buttons_ptr, count, |button| {
+                Action::try_from(button as i32).unwrap_or(Action::Release)
+            })
+        }
+    }
+
+    #[doc(alias = "glfwGetJoystickHats")]
+    pub fn hats(&self) -> Option<Vec<HatState>> {
+        unsafe {
+            let mut count = 0;
+            let hats_ptr = sys::glfwGetJoystickHats(self.id as i32, &mut count);
+            clear_joystick_error("glfwGetJoystickHats");
+            joystick_array(hats_ptr, count, HatState::from_bits_truncate)
+        }
+    }
+
+    pub fn state(&self) -> Option<JoystickState> {
+        Some(JoystickState {
+            axes: self.axes()?,
+            buttons: self.buttons()?,
+            hats: self.hats()?,
+        })
+    }
+
+    #[doc(alias = "glfwGetJoystickName")]
+    pub fn name(&self) -> Option<String> {
+        unsafe {
+            let name_ptr = sys::glfwGetJoystickName(self.id as i32);
+            clear_joystick_error("glfwGetJoystickName");
+            joystick_string(name_ptr)
+        }
+    }
+
+    #[doc(alias = "glfwGetJoystickGUID")]
+    pub fn guid(&self) -> Option<JoystickGuid> {
+        unsafe {
+            let guid_ptr = sys::glfwGetJoystickGUID(self.id as i32);
+            clear_joystick_error("glfwGetJoystickGUID");
+            let guid = joystick_string(guid_ptr)?;
+            match guid.parse() {
+                Ok(guid) => Some(guid),
+                Err(err) => {
+                    log::warn!("invalid joystick GUID {:?}: {}", guid, err);
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl Glfw {
+    pub fn joystick(&self, id: JoystickId) -> Joystick {
+        Joystick {
+            id,
+            _terminate: Rc::clone(&self.terminate),
+        }
+    }
+
+    /// The joysticks that are currently connected.
+    pub fn joysticks(&self) -> Vec<Joystick> {
+        JoystickId::all()
+            .map(|id| self.joystick(id))
+            .filter(Joystick::is_present)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn joystick_guid() {
+        // Xbox 360 controller on Linux
+        let guid: JoystickGuid = "030000005e0400008e02000014010000".parse().unwrap();
+        assert_eq!(0x03, guid.bus());
+        assert_eq!(Some(0x045e), guid.vendor());
+        assert_eq!(Some(0x028e), guid.product());
+        assert_eq!(Some(0x0114), guid.version());
+        assert_eq!("030000005e0400008e02000014010000", guid.to_string());
+
+        // XInput device on Windows
+        let guid: JoystickGuid = "78696e70757401000000000000000000".parse().unwrap();
+        assert_eq!(None, guid.vendor());
+
+        assert!("0300".parse::<JoystickGuid>().is_err());
+        assert!("zz0000005e0400008e02000014010000"
+            .parse::<JoystickGuid>()
+            .is_err());
+    }
+
+    #[test]
+    fn joystick_id() {
+        assert_eq!(16, JoystickId::all().count());
+        assert_eq!(Ok(JoystickId::Joystick16), JoystickId::try_from(15));
+        assert_eq!(Err(16), JoystickId::try_from(16));
+    }
+}
@@ -1,10 +1,11 @@
-use core::ffi::{c_int, CStr};
+use core::ffi::{c_char, c_int, CStr};
 use glfw3_sys::{self as sys};
 use std::{
     ffi::CString,
     fmt::Pointer,
     marker::PhantomData,
     mem,
+    ops::RangeInclusive,
     path::PathBuf,
     ptr,
     rc::Rc,
@@ -12,11 +13,41 @@ use std::{
     time::Duration,
 };
 
+mod calibration;
 mod callbacks;
+mod cursor;
+mod gamepad;
+mod gesture;
+mod image;
+mod input;
+mod input_map;
+mod input_state;
+mod joystick;
+mod mapping;
 mod monitor;
+mod osmesa;
+pub mod platform;
+#[cfg(feature = "raw-window-handle")]
+mod raw_handle;
+mod shortcut;
+mod tracker;
+mod vulkan;
 mod window;
 
+pub use calibration::*;
+pub use cursor::*;
+pub use gamepad::*;
+pub use gesture::*;
+pub use image::*;
+pub use input::*;
+pub use input_map::*;
+pub use input_state::*;
+pub use joystick::*;
+pub use mapping::*;
 pub use monitor::*;
+pub use shortcut::*;
+pub use tracker::*;
+pub use vulkan::*;
 pub use window::*;
 
 /// Unwrap errors that are expected to be impossible to happen unless
@@ -44,6 +75,7 @@ pub struct Glfw {
 
 impl Drop for Terminate {
     fn drop(&mut self) {
+        tracker::clear_tracker();
         unsafe {
             sys::glfwTerminate();
             if let Some(err) = Glfw::get_error().err() {
@@ -176,6 +208,27 @@ fn unknown_error() -> Error {
     }
 }
 
+/// Converts text for GLFW, which cannot represent interior nul bytes.
+pub(crate) fn text_to_c_string(text: &str) -> Result<CString, Error> {
+    CString::new(text).map_err(|err| Error {
+        code: sys::GLFW_INVALID_VALUE,
+        desc: err.to_string(),
+    })
+}
+
+/// Reads a string returned by a clipboard getter, where `GLFW_FORMAT_UNAVAILABLE`
+/// means the clipboard is empty or does not hold text.
+pub(crate) unsafe fn clipboard_string(string_ptr: *const c_char) -> Result<Option<String>, Error> {
+    match Glfw::get_error() {
+        Err(err) if sys::GLFW_FORMAT_UNAVAILABLE == err.code => Ok(None),
+        Err(err) => Err(err),
+        Ok(()) if string_ptr.is_null() => Ok(None),
+        Ok(()) => Ok(Some(
+            CStr::from_ptr(string_ptr).to_string_lossy().into_owned(),
+        )),
+    }
+}
+
 fn initialize<'a>(hints: &'a [InitHint], init_guard: InitGuard) -> Result<Glfw, InitError<'a>> {
     let default_hints = InitHint::default_hints();
     for hint in default_hints.iter().chain(hints.iter()) {
@@ -205,6 +258,19 @@ fn initialize<'a>(hints: &'a [InitHint], init_guard: InitGuard) -> Result<Glfw,
     }
 }
 
+/// The platform selected by `glfwInit`, for types that outlive a `&Glfw` borrow.
+pub(crate) fn current_platform() -> Platform {
+    let platform = unsafe { sys::glfwGetPlatform() };
+    match platform {
+        sys::GLFW_PLATFORM_WIN32 => Platform::Win32,
+        sys::GLFW_PLATFORM_COCOA => Platform::Cocoa,
+        sys::GLFW_PLATFORM_WAYLAND => Platform::Wayland,
+        sys::GLFW_PLATFORM_X11 => Platform::X11,
+        sys::GLFW_PLATFORM_NULL => Platform::Null,
+        _ => Platform::Any,
+    }
+}
+
 impl Glfw {
     #[doc(alias = "glfwGetError")]
     pub fn get_error() -> Result<(), Error> {
@@ -255,15 +321,7 @@ impl Glfw {
 
     #[doc(alias = "glfwGetPlatform")]
     pub fn get_platform(&self) -> Platform {
-        let platform = unsafe { sys::glfwGetPlatform() };
-        match platform {
-            sys::GLFW_PLATFORM_WIN32 => Platform::Win32,
-            sys::GLFW_PLATFORM_COCOA => Platform::Cocoa,
-            sys::GLFW_PLATFORM_WAYLAND => Platform::Wayland,
-            sys::GLFW_PLATFORM_X11 => Platform::X11,
-            sys::GLFW_PLATFORM_NULL => Platform::Null,
-            _ => Platform::Any,
-        }
+        current_platform()
     }
 
     #[doc(alias = "glfwCreateWindow")]
@@ -283,10 +341,26 @@ impl Glfw {
             Glfw::get_error().expect(GLFW_NOT_INITIALIZED);
             for hint in hints.iter() {
                 match hint {
+                    &WindowHint::Visible(visible) => {
+                        let value = if visible {
+                            sys::GLFW_TRUE
+                        } else {
+                            sys::GLFW_FALSE
+                        };
+                        sys::glfwWindowHint(sys::GLFW_VISIBLE, value);
+                        Glfw::get_error().map_err(|err| CreateWindowError::Hint(hint, err))?;
+                    }
                     &WindowHint::ClientApi(client_api) => {
                         sys::glfwWindowHint(sys::GLFW_CLIENT_API, client_api as i32);
                         Glfw::get_error().map_err(|err| CreateWindowError::Hint(hint, err))?;
                     }
+                    &WindowHint::ContextCreationApi(context_creation_api) => {
+                        sys::glfwWindowHint(
+                            sys::GLFW_CONTEXT_CREATION_API,
+                            context_creation_api as i32,
+                        );
+                        Glfw::get_error().map_err(|err| CreateWindowError::Hint(hint, err))?;
+                    }
                     _ => {}
                 }
             }
@@ -335,27 +409,52 @@ impl Glfw {
         }
     }
 
+    /// Returns `None` if the clipboard is empty or its contents are not text.
+    #[doc(alias = "glfwGetClipboardString")]
+    pub fn clipboard(&self) -> Result<Option<String>, Error> {
+        unsafe {
+            let string_ptr = sys::glfwGetClipboardString(ptr::null_mut());
+            clipboard_string(string_ptr)
+        }
+    }
+
+    #[doc(alias = "glfwSetClipboardString")]
+    pub fn set_clipboard(&self, text: &str) -> Result<(), Error> {
+        let text = text_to_c_string(text)?;
+        unsafe {
+            sys::glfwSetClipboardString(ptr::null_mut(), text.as_ptr());
+            Glfw::get_error()
+        }
+    }
+
     pub fn poll_events<F>(&self, event_handler: &mut F) -> Result<(), Error>
     where
-        F: FnMut(WindowId, (f64, WindowEvent)) -> Option<(f64, WindowEvent)>,
+        F: FnMut((f64, Event)) -> Option<(f64, Event)>,
     {
         let _unset_handler_guard = callbacks::set_handler(event_handler);
         unsafe {
             sys::glfwPollEvents();
             Glfw::get_error()?;
         }
+        cursor::animate_cursors();
+        tracker::track_gamepads();
         Ok(())
     }
 
     pub fn wait_events<F>(&self, event_handler: &mut F) -> Result<(), Error>
     where
-        F: FnMut(WindowId, (f64, WindowEvent)) -> Option<(f64, WindowEvent)>,
+        F: FnMut((f64, Event)) -> Option<(f64, Event)>,
     {
         let _unset_handler_guard = callbacks::set_handler(event_handler);
         unsafe {
-            sys::glfwWaitEvents();
+            match cursor::animate_cursors() {
+                Some(next_frame) => sys::glfwWaitEventsTimeout(next_frame),
+                None => sys::glfwWaitEvents(),
+            }
             Glfw::get_error()?;
         }
+        cursor::animate_cursors();
+        tracker::track_gamepads();
         Ok(())
     }
 
@@ -365,13 +464,19 @@ impl Glfw {
         event_handler: &mut F,
     ) -> Result<(), Error>
     where
-        F: FnMut(WindowId, (f64, WindowEvent)) -> Option<(f64, WindowEvent)>,
+        F: FnMut((f64, Event)) -> Option<(f64, Event)>,
     {
         let _unset_handler_guard = callbacks::set_handler(event_handler);
+        let mut timeout = timeout.as_secs_f64();
+        if let Some(next_frame) = cursor::animate_cursors() {
+            timeout = timeout.min(next_frame);
+        }
         unsafe {
-            sys::glfwWaitEventsTimeout(timeout.as_secs_f64());
+            sys::glfwWaitEventsTimeout(timeout);
             Glfw::get_error()?;
         }
+        cursor::animate_cursors();
+        tracker::track_gamepads();
         Ok(())
     }
 }
@@ -440,9 +545,10 @@ pub enum ClientApi {
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContextCreationApi {
-    Native,
-    Egl,
-    OsMesa,
+    Native = sys::GLFW_NATIVE_CONTEXT_API,
+    Egl = sys::GLFW_EGL_CONTEXT_API,
+    /// Software rendering that needs no display or GPU, see [`Window::osmesa_color_buffer`].
+    OsMesa = sys::GLFW_OSMESA_CONTEXT_API,
 }
 
 #[repr(i32)]
@@ -483,6 +589,8 @@ mod tests {
     assert_not_impl!(Terminate, Send, Sync);
     assert_not_impl!(Window, Send, Sync);
     assert_not_impl!(Monitor, Send, Sync);
+    assert_not_impl!(Cursor, Send, Sync);
+    assert_not_impl!(Joystick, Send, Sync);
 
     const INIT_HINTS: &[InitHint] = &[InitHint::Platform(Platform::Null)];
 
@@ -544,10 +652,160 @@ mod tests {
             )
             .expect("create_window");
     }
+
+    #[test]
+    fn joystick_event() {
+        let _glfw = Glfw::init(INIT_HINTS).unwrap();
+        let mut events = Vec::new();
+        let mut handler = |(_time, event)| {
+            events.push(event);
+            None
+        };
+        let guard = callbacks::set_handler(&mut handler);
+        unsafe {
+            callbacks::joystick_callback(sys::GLFW_JOYSTICK_2, sys::GLFW_CONNECTED);
+            callbacks::joystick_callback(sys::GLFW_JOYSTICK_2, sys::GLFW_DISCONNECTED);
+        }
+        drop(guard);
+        assert_eq!(
+            vec![
+                Event::Joystick(JoystickEvent::Connected(JoystickId::Joystick2)),
+                Event::Joystick(JoystickEvent::Disconnected(JoystickId::Joystick2)),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn clipboard() {
+        let glfw = Glfw::init(INIT_HINTS).unwrap();
+        glfw.set_clipboard("clipboard text").unwrap();
+        assert_eq!(Some("clipboard text"), glfw.clipboard().unwrap().as_deref());
+        assert!(glfw.set_clipboard("nul\0byte").is_err());
+    }
+
+    #[test]
+    fn update_gamepad_mappings() {
+        let glfw = Glfw::init(INIT_HINTS).unwrap();
+        // GLFW ignores the misc1 and paddle bindings of SDL_GameControllerDB
+        let mappings = "# Xbox\n\
+            050000005e040000220b000013050000,Xbox Elite Series 2 Controller,a:b0,b:b1,back:b10,\
+            dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b12,leftshoulder:b6,\
+            leftstick:b13,lefttrigger:a5,leftx:a0,lefty:a1,misc1:b15,paddle1:b16,paddle2:b18,\
+            paddle3:b17,paddle4:b19,rightshoulder:b7,rightstick:b14,righttrigger:a4,rightx:a2,\
+            righty:a3,start:b11,x:b3,y:b4,platform:Linux,\n";
+        glfw.update_gamepad_mappings(mappings).unwrap();
+        let errors = glfw
+            .update_gamepad_mappings("0300,Pad,a:b0,\n")
+            .unwrap_err();
+        assert_eq!(1, errors[0].line);
+    }
+
+    #[test]
+    fn cursor_grab() {
+        let glfw = Glfw::init(INIT_HINTS).unwrap();
+        let window = glfw
+            .create_window(
+                &[WindowHint::ClientApi(ClientApi::None)],
+                800,
+                600,
+                "test",
+                None,
+                None,
+            )
+            .expect("create_window");
+        assert_eq!(CursorMode::Normal, window.cursor_mode());
+        {
+            let grab = window.grab_cursor(CursorMode::Disabled).unwrap();
+            assert_eq!(CursorMode::Normal, grab.previous());
+            assert_eq!(CursorMode::Disabled, window.cursor_mode());
+        }
+        assert_eq!(CursorMode::Normal, window.cursor_mode());
+        window.set_sticky_keys(true).unwrap();
+        assert!(window.sticky_keys());
+    }
+
+    #[test]
+    fn all_keys() {
+        assert!(Key::all().all(|key| Ok(key) == Key::try_from(key as i32)));
+        assert!(!Key::all().any(|key| Key::Unknown == key));
+        assert_eq!(Some(Key::Menu), Key::all().last());
+        assert_eq!(Ok(Key::Unknown), Key::try_from(sys::GLFW_KEY_UNKNOWN));
+        assert_eq!(Err(1), Key::try_from(1));
+    }
+
+    #[test]
+    fn create_cursor() {
+        let glfw = Glfw::init(INIT_HINTS).unwrap();
+        let window = glfw
+            .create_window(
+                &[WindowHint::ClientApi(ClientApi::None)],
+                800,
+                600,
+                "test",
+                None,
+                None,
+            )
+            .expect("create_window");
+        let image = Image::new(16, 16, vec![[255, 0, 0, 255]; 16 * 16]).unwrap();
+        let cursor = glfw.create_cursor(&image, 8, 8).expect("create_cursor");
+        window.set_cursor(Some(&cursor)).unwrap();
+        let standard = glfw
+            .create_standard_cursor_or_fallback(StandardCursor::NotAllowed)
+            .expect("create_standard_cursor_or_fallback");
+        window.set_cursor(Some(&standard)).unwrap();
+        window.set_cursor(None).unwrap();
+    }
+
+    #[test]
+    fn cursor_frame_at() {
+        use crate::cursor::frame_at;
+
+        assert_eq!((0, None), frame_at(&[0.5], 10.0));
+        assert_eq!((0, None), frame_at(&[0.0, 0.0], 10.0));
+        assert_eq!((0, Some(0.5)), frame_at(&[0.5, 0.25], 0.0));
+        assert_eq!((1, Some(0.25)), frame_at(&[0.5, 0.25], 0.5));
+        assert_eq!((0, Some(0.5)), frame_at(&[0.5, 0.25], 0.75));
+    }
+
+    #[test]
+    fn known_key() {
+        assert_eq!(Some(Key::W), Key::W.known().map(KnownKey::key));
+        assert_eq!(None, Key::Unknown.known());
+    }
+
+    #[test]
+    fn osmesa_color_buffer() {
+        let glfw = Glfw::init(INIT_HINTS).unwrap();
+        let hints = [
+            WindowHint::Visible(false),
+            WindowHint::ContextCreationApi(ContextCreationApi::OsMesa),
+        ];
+        let window = match glfw.create_window(&hints, 64, 48, "osmesa", None, None) {
+            Ok(window) => window,
+            // libOSMesa is loaded at runtime and may not be installed
+            Err(CreateWindowError::CreateWindow(err)) if err.code == sys::GLFW_API_UNAVAILABLE => {
+                return
+            }
+            Err(err) => panic!("create_window: {:?}", err),
+        };
+        assert!(!window.osmesa_context().unwrap().is_null());
+        unsafe {
+            Window::make_context_current(Some(window.window_id())).unwrap();
+        }
+        let color = window.osmesa_color_buffer().expect("osmesa_color_buffer");
+        assert_eq!((64, 48), (color.width(), color.height()));
+        let depth = window.osmesa_depth_buffer().expect("osmesa_depth_buffer");
+        assert_eq!((64, 48), (depth.width(), depth.height()));
+        unsafe {
+            Window::make_context_current(None).unwrap();
+        }
+    }
 }
 
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     Space = sys::GLFW_KEY_SPACE,
 
@@ -682,29 +940,46 @@ pub enum Key {
     RightSuper = sys::GLFW_KEY_RIGHT_SUPER,
     Menu = sys::GLFW_KEY_MENU,
 
+    /// A key GLFW has no name for, such as a media key. Use the scancode to tell these apart.
     Unknown = sys::GLFW_KEY_UNKNOWN,
 }
 
+/// The values of every `Key` except `Key::Unknown`.
+const KEY_RANGES: [RangeInclusive<i32>; 13] = [
+    32..=32,
+    39..=39,
+    44..=57,
+    59..=59,
+    61..=61,
+    65..=93,
+    96..=96,
+    161..=162,
+    256..=269,
+    280..=284,
+    290..=314,
+    320..=336,
+    340..=348,
+];
+
+impl Key {
+    /// Every key except [`Key::Unknown`], in ascending order.
+    pub fn all() -> impl Iterator<Item = Key> {
+        KEY_RANGES
+            .into_iter()
+            .flatten()
+            .map(|value| unsafe { mem::transmute::<i32, Key>(value) })
+    }
+}
+
 impl TryFrom<i32> for Key {
     type Error = i32;
 
     fn try_from(value: i32) -> Result<Self, Self::Error> {
-        let ranges = [
-            32..=32,
-            39..=39,
-            44..=57,
-            59..=59,
-            61..=61,
-            65..=93,
-            96..=96,
-            161..=162,
-            256..=269,
-            280..=284,
-            290..=314,
-            320..=336,
-            340..=348,
-        ];
-        for range in ranges {
+        // Keys without a `Key` value still carry a valid scancode
+        if sys::GLFW_KEY_UNKNOWN == value {
+            return Ok(Key::Unknown);
+        }
+        for range in KEY_RANGES {
             if range.contains(&value) {
                 return Ok(unsafe { mem::transmute(value) });
             }
@@ -722,6 +997,7 @@ pub use self::MouseButton::Button3 as MouseButtonMiddle;
 
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     /// The left mouse button. A `MouseButtonLeft` alias is provided to improve clarity.
     Button1 = sys::GLFW_MOUSE_BUTTON_1,
@@ -750,7 +1026,9 @@ impl TryFrom<i32> for MouseButton {
 #[derive(Clone, PartialEq, Debug)]
 pub enum Event {
     Monitor,
-    Window(WindowEvent),
+    Joystick(JoystickEvent),
+    Gamepad(JoystickId, GamepadEvent),
+    Window(WindowId, WindowEvent),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -770,6 +1048,12 @@ pub enum WindowEvent {
     FramebufferSize(i32, i32),
     MouseButton(MouseButton, Action, Modifiers),
     CursorPos(f64, f64),
+    /// The cursor movement since the previous `CursorPos` event. Unlike the
+    /// position, this does not jump when the cursor mode changes, the cursor
+    /// is moved with [`Window::set_cursor_pos`] or the window regains focus.
+    /// With [`Window::set_raw_mouse_motion`] and [`CursorMode::Disabled`] it
+    /// is the unscaled motion of the mouse.
+    MouseMotion(f64, f64),
     CursorEnter(bool),
     Scroll(f64, f64),
     Key(Key, Scancode, Action, Modifiers),
@@ -781,14 +1065,19 @@ pub enum WindowEvent {
     ContentScale(f32, f32),
 }
 
-pub type Scancode = core::ffi::c_int;
+/// A platform-specific identifier for a physical key, stable across keyboard layouts.
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scancode(pub i32);
 
 /// Native endian UTF-32
 pub type Codepoint = core::ffi::c_uint;
 
 bitflags::bitflags! {
     #[repr(transparent)]
-    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Modifiers: i32 {
         const SHIFT     = sys::GLFW_MOD_SHIFT;
         const CONTROL   = sys::GLFW_MOD_CONTROL;
@@ -830,6 +1119,6 @@ pub unsafe fn set_global_callbacks() -> Result<(), Error> {
     // sys::glfwSetErrorCallback(callback);
     sys::glfwSetMonitorCallback(Some(monitor_callback));
     Glfw::get_error()?;
-    // sys::glfwSetJoystickCallback(Some(callback));
-    Ok(())
+    sys::glfwSetJoystickCallback(Some(callbacks::joystick_callback));
+    Glfw::get_error()
 }
@@ -0,0 +1,506 @@
+use core::{fmt, str::FromStr};
+
+use crate::{GamepadAxis, GamepadButton, HatState, JoystickGuid, ParseGuidError};
+
+/// Indexed by discriminant.
+const BUTTON_NAMES: [(GamepadButton, &str); 15] = [
+    (GamepadButton::A, "a"),
+    (GamepadButton::B, "b"),
+    (GamepadButton::X, "x"),
+    (GamepadButton::Y, "y"),
+    (GamepadButton::LeftBumper, "leftshoulder"),
+    (GamepadButton::RightBumper, "rightshoulder"),
+    (GamepadButton::Back, "back"),
+    (GamepadButton::Start, "start"),
+    (GamepadButton::Guide, "guide"),
+    (GamepadButton::LeftThumb, "leftstick"),
+    (GamepadButton::RightThumb, "rightstick"),
+    (GamepadButton::DpadUp, "dpup"),
+    (GamepadButton::DpadRight, "dpright"),
+    (GamepadButton::DpadDown, "dpdown"),
+    (GamepadButton::DpadLeft, "dpleft"),
+];
+
+const AXIS_NAMES: [(GamepadAxis, &str); 6] = [
+    (GamepadAxis::LeftX, "leftx"),
+    (GamepadAxis::LeftY, "lefty"),
+    (GamepadAxis::RightX, "rightx"),
+    (GamepadAxis::RightY, "righty"),
+    (GamepadAxis::LeftTrigger, "lefttrigger"),
+    (GamepadAxis::RightTrigger, "righttrigger"),
+];
+
+/// The part of an axis a binding covers, written as a `+` or `-` prefix.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisRange {
+    Full,
+    Positive,
+    Negative,
+}
+
+impl AxisRange {
+    fn split_prefix(text: &str) -> (AxisRange, &str) {
+        if let Some(rest) = text.strip_prefix('+') {
+            (AxisRange::Positive, rest)
+        } else if let Some(rest) = text.strip_prefix('-') {
+            (AxisRange::Negative, rest)
+        } else {
+            (AxisRange::Full, text)
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            AxisRange::Full => "",
+            AxisRange::Positive => "+",
+            AxisRange::Negative => "-",
+        }
+    }
+}
+
+/// A gamepad input, the left-hand side of a binding.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum MappingTarget {
+    Button(GamepadButton),
+    Axis(GamepadAxis, AxisRange),
+}
+
+impl FromStr for MappingTarget {
+    type Err = GamepadMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (range, name) = AxisRange::split_prefix(s);
+        if let Some(&(axis, _)) = AXIS_NAMES.iter().find(|(_, n)| *n == name) {
+            return Ok(MappingTarget::Axis(axis, range));
+        }
+        match BUTTON_NAMES.iter().find(|(_, n)| *n == name) {
+            Some(&(button, _)) if AxisRange::Full == range => Ok(MappingTarget::Button(button)),
+            _ => Err(GamepadMappingError::UnknownTarget(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for MappingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MappingTarget::Button(button) => {
+                let (_, name) = BUTTON_NAMES[button as usize];
+                f.write_str(name)
+            }
+            MappingTarget::Axis(axis, range) => {
+                let (_, name) = AXIS_NAMES[axis as usize];
+                write!(f, "{}{}", range.prefix(), name)
+            }
+        }
+    }
+}
+
+/// A joystick input, the right-hand side of a binding.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MappingSource {
+    /// `b0`
+    Button(u8),
+    /// `h0.1`
+    Hat(u8, HatState),
+    /// `a0`, `+a0`, `-a0` and `a0~` for an inverted axis.
+    Axis {
+        axis: u8,
+        range: AxisRange,
+        inverted: bool,
+    },
+}
+
+impl FromStr for MappingSource {
+    type Err = GamepadMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GamepadMappingError::InvalidSource(s.into());
+        let (range, rest) = AxisRange::split_prefix(s);
+        let (inverted, rest) = match rest.strip_suffix('~') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let index = |digits: &str| -> Result<u8, GamepadMappingError> {
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            digits.parse().map_err(|_| invalid())
+        };
+        if let Some(axis) = rest.strip_prefix('a') {
+            return Ok(MappingSource::Axis {
+                axis: index(axis)?,
+                range,
+                inverted,
+            });
+        }
+        if AxisRange::Full != range || inverted {
+            return Err(invalid());
+        }
+        if let Some(button) = rest.strip_prefix('b') {
+            Ok(MappingSource::Button(index(button)?))
+        } else if let Some(hat) = rest.strip_prefix('h') {
+            let (hat, state) = hat.split_once('.').ok_or_else(invalid)?;
+            let state = HatState::from_bits(index(state)?)
+                .filter(|state| !state.is_empty())
+                .ok_or_else(invalid)?;
+            Ok(MappingSource::Hat(index(hat)?, state))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl fmt::Display for MappingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MappingSource::Button(button) => write!(f, "b{}", button),
+            MappingSource::Hat(hat, state) => write!(f, "h{}.{}", hat, state.bits()),
+            MappingSource::Axis {
+                axis,
+                range,
+                inverted,
+            } => {
+                let suffix = if inverted { "~" } else { "" };
+                write!(f, "{}a{}{}", range.prefix(), axis, suffix)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GamepadMappingError {
+    Guid(ParseGuidError),
+    /// The name is missing or contains a comma.
+    InvalidName(String),
+    /// A field is not of the form `key:value`.
+    InvalidField(String),
+    UnknownTarget(String),
+    InvalidSource(String),
+}
+
+impl fmt::Display for GamepadMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GamepadMappingError::Guid(err) => fmt::Display::fmt(err, f),
+            GamepadMappingError::InvalidName(name) => {
+                write!(
+                    f,
+                    "invalid name {:?}: it must be non-empty without commas",
+                    name
+                )
+            }
+            GamepadMappingError::InvalidField(field) => {
+                write!(f, "expected `key:value`, found {:?}", field)
+            }
+            GamepadMappingError::UnknownTarget(target) => {
+                write!(f, "unknown gamepad button or axis {:?}", target)
+            }
+            GamepadMappingError::InvalidSource(source) => {
+                write!(f, "invalid joystick input {:?}", source)
+            }
+        }
+    }
+}
+
+impl core::error::Error for GamepadMappingError {}
+
+/// GLFW truncates names to 127 bytes, so longer ones are accepted.
+fn check_name(name: &str) -> Result<(), GamepadMappingError> {
+    if name.is_empty() || name.contains(',') {
+        return Err(GamepadMappingError::InvalidName(name.into()));
+    }
+    Ok(())
+}
+
+/// A mapping in the SDL_GameControllerDB format, e.g.
+/// `030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,leftx:a0,platform:Linux,`.
+///
+/// Apply it with [`Glfw::update_gamepad_mapping`](crate::Glfw::update_gamepad_mapping).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadMapping {
+    guid: JoystickGuid,
+    name: String,
+    bindings: Vec<(MappingTarget, MappingSource)>,
+    metadata: Vec<(String, String)>,
+}
+
+impl GamepadMapping {
+    pub fn new(guid: JoystickGuid, name: &str) -> Result<GamepadMapping, GamepadMappingError> {
+        check_name(name)?;
+        Ok(GamepadMapping {
+            guid,
+            name: name.into(),
+            bindings: Vec::new(),
+            metadata: Vec::new(),
+        })
+    }
+
+    pub fn guid(&self) -> JoystickGuid {
+        self.guid
+    }
+
+    pub fn set_guid(&mut self, guid: JoystickGuid) {
+        self.guid = guid;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) -> Result<(), GamepadMappingError> {
+        check_name(name)?;
+        self.name = name.into();
+        Ok(())
+    }
+
+    /// The platform the mapping is restricted to, such as `Linux`, `Windows`
+    /// or `Mac OS X`. GLFW ignores mappings for other platforms.
+    pub fn platform(&self) -> Option<&str> {
+        self.metadata("platform")
+    }
+
+    pub fn set_platform(&mut self, platform: Option<&str>) -> Result<(), GamepadMappingError> {
+        match platform {
+            Some(platform) if platform.is_empty() || platform.contains(',') => Err(
+                GamepadMappingError::InvalidField(format!("platform:{}", platform)),
+            ),
+            Some(platform) => {
+                self.set_metadata("platform", platform);
+                Ok(())
+            }
+            None => {
+                self.metadata.retain(|(key, _)| "platform" != key);
+                Ok(())
+            }
+        }
+    }
+
+    /// Other fields such as `crc` or `hint`, including bindings GLFW does not
+    /// support, such as `misc1` or `paddle1`. They are kept so that mappings
+    /// round-trip.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn set_metadata(&mut self, key: &str, value: &str) {
+        match self.metadata.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.into(),
+            None => self.metadata.push((key.into(), value.into())),
+        }
+    }
+
+    pub fn binding(&self, target: MappingTarget) -> Option<MappingSource> {
+        self.bindings
+            .iter()
+            .find(|(t, _)| *t == target)
+            .map(|&(_, source)| source)
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (MappingTarget, MappingSource)> + '_ {
+        self.bindings.iter().copied()
+    }
+
+    /// Binds the target, replacing and returning its previous source.
+    pub fn bind(&mut self, target: MappingTarget, source: MappingSource) -> Option<MappingSource> {
+        match self.bindings.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, s)) => Some(core::mem::replace(s, source)),
+            None => {
+                self.bindings.push((target, source));
+                None
+            }
+        }
+    }
+
+    pub fn unbind(&mut self, target: MappingTarget) -> Option<MappingSource> {
+        let index = self.bindings.iter().position(|(t, _)| *t == target)?;
+        Some(self.bindings.remove(index).1)
+    }
+}
+
+impl FromStr for GamepadMapping {
+    type Err = GamepadMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.trim().split(',');
+        let guid = fields
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(GamepadMappingError::Guid)?;
+        let mut mapping = GamepadMapping::new(guid, fields.next().unwrap_or_default())?;
+        for field in fields.filter(|field| !field.is_empty()) {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| GamepadMappingError::InvalidField(field.into()))?;
+            match key.parse() {
+                // Like GLFW, the first binding of a target wins
+                Ok(target) => {
+                    let source = value.parse()?;
+                    if mapping.binding(target).is_none() {
+                        mapping.bind(target, source);
+                    }
+                }
+                Err(_) => mapping.set_metadata(key, value),
+            }
+        }
+        Ok(mapping)
+    }
+}
+
+impl fmt::Display for GamepadMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},", self.guid, self.name)?;
+        for (target, source) in self.bindings.iter() {
+            write!(f, "{}:{},", target, source)?;
+        }
+        for (key, value) in self.metadata.iter() {
+            write!(f, "{}:{},", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const XBOX_360: &str = "030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,b:b1,\
+        dpup:h0.1,leftx:a0,lefty:a1,lefttrigger:a2,+righty:b5,-righty:b4,righttrigger:a5~,\
+        platform:Linux,";
+
+    #[test]
+    fn parse_mapping() {
+        let mapping: GamepadMapping = XBOX_360.parse().unwrap();
+        assert_eq!("Xbox 360 Controller", mapping.name());
+        assert_eq!(Some(0x045e), mapping.guid().vendor());
+        assert_eq!(Some("Linux"), mapping.platform());
+        assert_eq!(
+            Some(MappingSource::Button(0)),
+            mapping.binding(MappingTarget::Button(GamepadButton::A))
+        );
+        assert_eq!(
+            Some(MappingSource::Hat(0, HatState::UP)),
+            mapping.binding(MappingTarget::Button(GamepadButton::DpadUp))
+        );
+        assert_eq!(
+            Some(MappingSource::Button(5)),
+            mapping.binding(MappingTarget::Axis(
+                GamepadAxis::RightY,
+                AxisRange::Positive
+            ))
+        );
+        assert_eq!(
+            Some(MappingSource::Axis {
+                axis: 5,
+                range: AxisRange::Full,
+                inverted: true
+            }),
+            mapping.binding(MappingTarget::Axis(
+                GamepadAxis::RightTrigger,
+                AxisRange::Full
+            ))
+        );
+    }
+
+    #[test]
+    fn half_axis_source() {
+        let source: MappingSource = "-a3~".parse().unwrap();
+        assert_eq!(
+            MappingSource::Axis {
+                axis: 3,
+                range: AxisRange::Negative,
+                inverted: true
+            },
+            source
+        );
+        assert_eq!("-a3~", source.to_string());
+    }
+
+    #[test]
+    fn serialize_mapping() {
+        let mapping: GamepadMapping = XBOX_360.parse().unwrap();
+        assert_eq!(XBOX_360, mapping.to_string());
+        assert_eq!(mapping, mapping.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn edit_mapping() {
+        let guid = "030000005e0400008e02000014010000".parse().unwrap();
+        let mut mapping = GamepadMapping::new(guid, "Remapped").unwrap();
+        let target = MappingTarget::Button(GamepadButton::Start);
+        assert_eq!(None, mapping.bind(target, MappingSource::Button(7)));
+        assert_eq!(
+            Some(MappingSource::Button(7)),
+            mapping.bind(target, MappingSource::Button(9))
+        );
+        mapping.set_platform(Some("Windows")).unwrap();
+        assert_eq!(
+            "030000005e0400008e02000014010000,Remapped,start:b9,platform:Windows,",
+            mapping.to_string()
+        );
+        assert_eq!(Some(MappingSource::Button(9)), mapping.unbind(target));
+        mapping.set_platform(None).unwrap();
+        assert_eq!(
+            "030000005e0400008e02000014010000,Remapped,",
+            mapping.to_string()
+        );
+        assert!(mapping.set_name("a,b").is_err());
+    }
+
+    #[test]
+    fn invalid_mapping() {
+        let parse = |s: &str| s.parse::<GamepadMapping>().unwrap_err();
+        assert_eq!(
+            GamepadMappingError::Guid(ParseGuidError),
+            parse("030000005e04,Pad,a:b0,")
+        );
+        assert_eq!(
+            GamepadMappingError::InvalidName("".into()),
+            parse("030000005e0400008e02000014010000,,a:b0,")
+        );
+        assert_eq!(
+            GamepadMappingError::InvalidField("a".into()),
+            parse("030000005e0400008e02000014010000,Pad,a,")
+        );
+        assert_eq!(
+            GamepadMappingError::InvalidSource("h0.0".into()),
+            parse("030000005e0400008e02000014010000,Pad,dpup:h0.0,")
+        );
+        assert_eq!(
+            GamepadMappingError::InvalidSource("b0~".into()),
+            parse("030000005e0400008e02000014010000,Pad,a:b0~,")
+        );
+    }
+
+    #[test]
+    fn unsupported_fields() {
+        const ELITE_2: &str = "050000005e040000220b000013050000,Xbox Elite Series 2 Controller,\
+            a:b0,b:b1,back:b10,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b12,\
+            leftshoulder:b6,leftstick:b13,lefttrigger:a5,leftx:a0,lefty:a1,misc1:b15,\
+            paddle1:b16,paddle2:b18,paddle3:b17,paddle4:b19,rightshoulder:b7,rightstick:b14,\
+            righttrigger:a4,rightx:a2,righty:a3,start:b11,x:b3,y:b4,platform:Linux,";
+        let mapping: GamepadMapping = ELITE_2.parse().unwrap();
+        assert_eq!(Some("b15"), mapping.metadata("misc1"));
+        assert_eq!(Some("b16"), mapping.metadata("paddle1"));
+        assert_eq!(Some("Linux"), mapping.platform());
+        assert_eq!(
+            Some(MappingSource::Button(12)),
+            mapping.binding(MappingTarget::Button(GamepadButton::Guide))
+        );
+        assert_eq!(mapping, mapping.to_string().parse().unwrap());
+
+        let mapping: GamepadMapping = "030000005e0400008e02000014010000,Pad,a:b0,a:b1,"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            Some(MappingSource::Button(0)),
+            mapping.binding(MappingTarget::Button(GamepadButton::A))
+        );
+        let name = "P".repeat(200);
+        assert!(GamepadMapping::new(mapping.guid(), &name).is_ok());
+    }
+}
@@ -0,0 +1,108 @@
+use core::{ffi::c_void, ptr, slice};
+
+use glfw3_sys::{self as sys, platform::osmesa};
+
+use crate::{unknown_error, Error, Glfw, Image, Window};
+
+/// `OSMESA_RGBA`, the only color format GLFW requests when creating an OSMesa context.
+const OSMESA_RGBA: i32 = 0x1908;
+
+/// Copies a buffer stored bottom-to-top, as OpenGL does, into a top-to-bottom image.
+unsafe fn flip_rows<T: Copy, P>(
+    buffer: *const T,
+    width: i32,
+    height: i32,
+    components: usize,
+    pixel: impl Fn(&[T]) -> P,
+) -> Image<P> {
+    let (width, height) = (width.max(0) as usize, height.max(0) as usize);
+    let row_len = width * components;
+    let buffer = unsafe { slice::from_raw_parts(buffer, row_len * height) };
+    let pixels = buffer
+        .chunks_exact(row_len.max(1))
+        .rev()
+        .flat_map(|row| row.chunks_exact(components).map(&pixel))
+        .collect();
+    Image::new(width as u32, height as u32, pixels).expect("buffer size mismatch")
+}
+
+impl Window {
+    /// Requires a window created with
+    /// [`ContextCreationApi::OsMesa`](crate::ContextCreationApi::OsMesa).
+    #[doc(alias = "glfwGetOSMesaContext")]
+    pub fn osmesa_context(&self) -> Result<osmesa::OSMesaContext, Error> {
+        unsafe {
+            let context = osmesa::glfwGetOSMesaContext(self.window_ptr);
+            Glfw::get_error()?;
+            Ok(context)
+        }
+    }
+
+    /// Reads back the RGBA color buffer of an OSMesa context.
+    ///
+    /// The buffer is allocated when the context is first made current, so
+    /// this fails until then.
+    #[doc(alias = "glfwGetOSMesaColorBuffer")]
+    pub fn osmesa_color_buffer(&self) -> Result<Image, Error> {
+        let mut width = 0;
+        let mut height = 0;
+        let mut format = 0;
+        let mut buffer: *mut c_void = ptr::null_mut();
+        unsafe {
+            let status = osmesa::glfwGetOSMesaColorBuffer(
+                self.window_ptr,
+                &mut width,
+                &mut height,
+                &mut format,
+                &mut buffer,
+            );
+            if sys::GLFW_TRUE != status || buffer.is_null() {
+                return Err(Glfw::get_error().err().unwrap_or_else(unknown_error));
+            }
+            if OSMESA_RGBA != format {
+                return Err(Error {
+                    code: sys::GLFW_FORMAT_UNAVAILABLE,
+                    desc: format!("Unsupported OSMesa color buffer format: {:#x}", format),
+                });
+            }
+            let pixel = |rgba: &[u8]| [rgba[0], rgba[1], rgba[2], rgba[3]];
+            Ok(flip_rows(buffer as *const u8, width, height, 4, pixel))
+        }
+    }
+
+    /// Reads back the raw depth values of an OSMesa context.
+    ///
+    /// 16-bit depth buffers are widened to `u32` without rescaling.
+    #[doc(alias = "glfwGetOSMesaDepthBuffer")]
+    pub fn osmesa_depth_buffer(&self) -> Result<Image<u32>, Error> {
+        let mut width = 0;
+        let mut height = 0;
+        let mut bytes_per_value = 0;
+        let mut buffer: *mut c_void = ptr::null_mut();
+        unsafe {
+            let status = osmesa::glfwGetOSMesaDepthBuffer(
+                self.window_ptr,
+                &mut width,
+                &mut height,
+                &mut bytes_per_value,
+                &mut buffer,
+            );
+            if sys::GLFW_TRUE != status || buffer.is_null() {
+                return Err(Glfw::get_error().err().unwrap_or_else(unknown_error));
+            }
+            match bytes_per_value {
+                2 => Ok(flip_rows(buffer as *const u16, width, height, 1, |d| {
+                    d[0] as u32
+                })),
+                4 => Ok(flip_rows(buffer as *const u32, width, height, 1, |d| d[0])),
+                _ => Err(Error {
+                    code: sys::GLFW_FORMAT_UNAVAILABLE,
+                    desc: format!(
+                        "Unsupported OSMesa depth buffer size: {} bytes",
+                        bytes_per_value
+                    ),
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Access to the native handles behind GLFW objects.
+//!
+//! Each extension trait checks [`Glfw::get_platform`](crate::Glfw::get_platform)
+//! before calling into the native API and returns a `GLFW_PLATFORM_UNAVAILABLE`
+//! [`Error`] when a different platform is in use.
+
+use glfw3_sys as sys;
+
+use crate::{current_platform, Error, Glfw, Platform};
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    doc
+))]
+pub mod wayland;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    doc
+))]
+pub mod x11;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Glfw {}
+    impl Sealed for crate::Window {}
+    impl Sealed for crate::Monitor {}
+}
+
+pub(crate) fn require_platform(platform: Platform) -> Result<(), Error> {
+    let current = current_platform();
+    if current == platform {
+        Ok(())
+    } else {
+        Err(Error {
+            code: sys::GLFW_PLATFORM_UNAVAILABLE,
+            desc: format!("{:?} is required but {:?} is in use", platform, current),
+        })
+    }
+}
+
+/// Calls a native getter after checking the platform, mapping null results to the GLFW error.
+pub(crate) unsafe fn native_ptr<T>(
+    platform: Platform,
+    getter: impl FnOnce() -> *mut T,
+) -> Result<*mut T, Error> {
+    require_platform(platform)?;
+    let ptr = getter();
+    Glfw::get_error()?;
+    if ptr.is_null() {
+        Err(crate::unknown_error())
+    } else {
+        Ok(ptr)
+    }
+}
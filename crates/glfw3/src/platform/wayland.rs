@@ -0,0 +1,50 @@
+//! Wayland handles.
+
+use glfw3_sys::platform::wayland as native;
+
+use super::{native_ptr, sealed::Sealed};
+use crate::{Error, Glfw, Monitor, Platform, Window};
+
+pub use native::{wl_display, wl_output, wl_surface};
+
+pub trait GlfwExtWayland: Sealed {
+    #[doc(alias = "glfwGetWaylandDisplay")]
+    fn wayland_display(&self) -> Result<*mut wl_display, Error>;
+}
+
+pub trait WindowExtWayland: Sealed {
+    /// The main `wl_surface` of the window.
+    #[doc(alias = "glfwGetWaylandWindow")]
+    fn wayland_window(&self) -> Result<*mut wl_surface, Error>;
+}
+
+pub trait MonitorExtWayland: Sealed {
+    #[doc(alias = "glfwGetWaylandMonitor")]
+    fn wayland_monitor(&self) -> Result<*mut wl_output, Error>;
+}
+
+impl GlfwExtWayland for Glfw {
+    fn wayland_display(&self) -> Result<*mut wl_display, Error> {
+        unsafe { native_ptr(Platform::Wayland, || native::glfwGetWaylandDisplay()) }
+    }
+}
+
+impl WindowExtWayland for Window {
+    fn wayland_window(&self) -> Result<*mut wl_surface, Error> {
+        unsafe {
+            native_ptr(Platform::Wayland, || {
+                native::glfwGetWaylandWindow(self.window_ptr)
+            })
+        }
+    }
+}
+
+impl MonitorExtWayland for Monitor {
+    fn wayland_monitor(&self) -> Result<*mut wl_output, Error> {
+        unsafe {
+            native_ptr(Platform::Wayland, || {
+                native::glfwGetWaylandMonitor(self.monitor_ptr)
+            })
+        }
+    }
+}
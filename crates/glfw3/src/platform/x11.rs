@@ -0,0 +1,110 @@
+//! X11 and GLX handles.
+//!
+//! X11 resource IDs are returned as `c_ulong`, which is how Xlib defines `XID`.
+
+use core::ffi::c_ulong;
+
+use glfw3_sys::platform::x11 as native;
+
+use super::{native_ptr, require_platform, sealed::Sealed};
+use crate::{clipboard_string, text_to_c_string, Error, Glfw, Monitor, Platform, Window};
+
+pub use native::{Display, GLXContext};
+
+pub trait GlfwExtX11: Sealed {
+    #[doc(alias = "glfwGetX11Display")]
+    fn x11_display(&self) -> Result<*mut Display, Error>;
+
+    /// The contents of the primary selection, or `None` if it is empty or not text.
+    #[doc(alias = "glfwGetX11SelectionString")]
+    fn x11_selection(&self) -> Result<Option<String>, Error>;
+
+    #[doc(alias = "glfwSetX11SelectionString")]
+    fn set_x11_selection(&self, text: &str) -> Result<(), Error>;
+}
+
+pub trait WindowExtX11: Sealed {
+    #[doc(alias = "glfwGetX11Window")]
+    fn x11_window(&self) -> Result<c_ulong, Error>;
+
+    /// Fails with `GLFW_NO_WINDOW_CONTEXT` unless the window has a GLX context.
+    #[doc(alias = "glfwGetGLXContext")]
+    fn glx_context(&self) -> Result<GLXContext, Error>;
+
+    /// Fails with `GLFW_NO_WINDOW_CONTEXT` unless the window has a GLX context.
+    #[doc(alias = "glfwGetGLXWindow")]
+    fn glx_window(&self) -> Result<c_ulong, Error>;
+}
+
+pub trait MonitorExtX11: Sealed {
+    /// The `RRCrtc` of the monitor.
+    #[doc(alias = "glfwGetX11Adapter")]
+    fn x11_adapter(&self) -> Result<c_ulong, Error>;
+
+    /// The `RROutput` of the monitor.
+    #[doc(alias = "glfwGetX11Monitor")]
+    fn x11_monitor(&self) -> Result<c_ulong, Error>;
+}
+
+impl GlfwExtX11 for Glfw {
+    fn x11_display(&self) -> Result<*mut Display, Error> {
+        unsafe { native_ptr(Platform::X11, || native::glfwGetX11Display()) }
+    }
+
+    fn x11_selection(&self) -> Result<Option<String>, Error> {
+        require_platform(Platform::X11)?;
+        unsafe {
+            let string_ptr = native::glfwGetX11SelectionString();
+            clipboard_string(string_ptr)
+        }
+    }
+
+    fn set_x11_selection(&self, text: &str) -> Result<(), Error> {
+        require_platform(Platform::X11)?;
+        let text = text_to_c_string(text)?;
+        unsafe {
+            native::glfwSetX11SelectionString(text.as_ptr());
+            Glfw::get_error()
+        }
+    }
+}
+
+impl WindowExtX11 for Window {
+    fn x11_window(&self) -> Result<c_ulong, Error> {
+        unsafe {
+            native_ptr(Platform::X11, || native::glfwGetX11Window(self.window_ptr))
+                .map(|xid| xid as usize as c_ulong)
+        }
+    }
+
+    fn glx_context(&self) -> Result<GLXContext, Error> {
+        unsafe { native_ptr(Platform::X11, || native::glfwGetGLXContext(self.window_ptr)) }
+    }
+
+    fn glx_window(&self) -> Result<c_ulong, Error> {
+        unsafe {
+            native_ptr(Platform::X11, || native::glfwGetGLXWindow(self.window_ptr))
+                .map(|xid| xid as usize as c_ulong)
+        }
+    }
+}
+
+impl MonitorExtX11 for Monitor {
+    fn x11_adapter(&self) -> Result<c_ulong, Error> {
+        unsafe {
+            native_ptr(Platform::X11, || {
+                native::glfwGetX11Adapter(self.monitor_ptr)
+            })
+            .map(|xid| xid as usize as c_ulong)
+        }
+    }
+
+    fn x11_monitor(&self) -> Result<c_ulong, Error> {
+        unsafe {
+            native_ptr(Platform::X11, || {
+                native::glfwGetX11Monitor(self.monitor_ptr)
+            })
+            .map(|xid| xid as usize as c_ulong)
+        }
+    }
+}
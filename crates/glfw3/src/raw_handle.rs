@@ -0,0 +1,128 @@
+use raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle,
+    RawWindowHandle, WindowHandle,
+};
+
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+use glfw3_sys::platform::{wayland, x11};
+
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+use crate::platform::{native_ptr, wayland::WindowExtWayland, x11::WindowExtX11};
+
+use crate::{current_platform, Glfw, Platform, Window};
+
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+fn non_null<T>(
+    ptr: Result<*mut T, crate::Error>,
+) -> Result<core::ptr::NonNull<core::ffi::c_void>, HandleError> {
+    ptr.ok()
+        .and_then(|ptr| core::ptr::NonNull::new(ptr as *mut core::ffi::c_void))
+        .ok_or(HandleError::Unavailable)
+}
+
+/// The default screen of the display GLFW opened with `XOpenDisplay(NULL)`,
+/// which Xlib takes from `$DISPLAY` in the form `host:display.screen`.
+/// Parsed here because GLFW loads libX11 at runtime instead of linking it.
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+fn x11_default_screen(display_name: Option<&str>) -> i32 {
+    display_name
+        .and_then(|name| name.rsplit_once(':'))
+        .and_then(|(_, display)| display.split_once('.'))
+        .and_then(|(_, screen)| screen.parse().ok())
+        .unwrap_or(0)
+}
+
+fn raw_display_handle() -> Result<RawDisplayHandle, HandleError> {
+    match current_platform() {
+        #[cfg(target_os = "windows")]
+        Platform::Win32 => Ok(raw_window_handle::WindowsDisplayHandle::new().into()),
+        #[cfg(target_os = "macos")]
+        Platform::Cocoa => Ok(raw_window_handle::AppKitDisplayHandle::new().into()),
+        #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+        Platform::X11 => {
+            let display =
+                non_null(unsafe { native_ptr(Platform::X11, || x11::glfwGetX11Display()) })?;
+            let display_name = std::env::var("DISPLAY").ok();
+            let screen = x11_default_screen(display_name.as_deref());
+            Ok(raw_window_handle::XlibDisplayHandle::new(Some(display), screen).into())
+        }
+        #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+        Platform::Wayland => {
+            let display = non_null(unsafe {
+                native_ptr(Platform::Wayland, || wayland::glfwGetWaylandDisplay())
+            })?;
+            Ok(raw_window_handle::WaylandDisplayHandle::new(display).into())
+        }
+        _ => Err(HandleError::NotSupported),
+    }
+}
+
+impl Window {
+    fn raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        match current_platform() {
+            #[cfg(target_os = "windows")]
+            Platform::Win32 => {
+                let hwnd =
+                    unsafe { glfw3_sys::platform::win32::glfwGetWin32Window(self.window_ptr) };
+                let hwnd =
+                    core::num::NonZeroIsize::new(hwnd as isize).ok_or(HandleError::Unavailable)?;
+                Ok(raw_window_handle::Win32WindowHandle::new(hwnd).into())
+            }
+            #[cfg(target_os = "macos")]
+            Platform::Cocoa => {
+                let ns_view =
+                    unsafe { glfw3_sys::platform::cocoa::glfwGetCocoaView(self.window_ptr) };
+                let ns_view = core::ptr::NonNull::new(ns_view).ok_or(HandleError::Unavailable)?;
+                Ok(raw_window_handle::AppKitWindowHandle::new(ns_view).into())
+            }
+            #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+            Platform::X11 => {
+                let window = self.x11_window().map_err(|_| HandleError::Unavailable)?;
+                Ok(raw_window_handle::XlibWindowHandle::new(window).into())
+            }
+            #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly"))]
+            Platform::Wayland => {
+                let surface = non_null(self.wayland_window())?;
+                Ok(raw_window_handle::WaylandWindowHandle::new(surface).into())
+            }
+            _ => Err(HandleError::NotSupported),
+        }
+    }
+}
+
+impl HasWindowHandle for Window {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        // The handle stays valid for as long as the window is borrowed
+        self.raw_window_handle()
+            .map(|raw| unsafe { WindowHandle::borrow_raw(raw) })
+    }
+}
+
+impl HasDisplayHandle for Window {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        raw_display_handle().map(|raw| unsafe { DisplayHandle::borrow_raw(raw) })
+    }
+}
+
+impl HasDisplayHandle for Glfw {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        raw_display_handle().map(|raw| unsafe { DisplayHandle::borrow_raw(raw) })
+    }
+}
+
+#[cfg(all(
+    test,
+    any(target_os = "linux", target_os = "freebsd", target_os = "dragonfly")
+))]
+mod tests {
+    use super::x11_default_screen;
+
+    #[test]
+    fn default_screen() {
+        assert_eq!(0, x11_default_screen(None));
+        assert_eq!(0, x11_default_screen(Some(":0")));
+        assert_eq!(1, x11_default_screen(Some(":0.1")));
+        assert_eq!(2, x11_default_screen(Some("localhost:10.2")));
+        assert_eq!(1, x11_default_screen(Some("[::1]:0.1")));
+    }
+}
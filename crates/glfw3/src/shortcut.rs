@@ -0,0 +1,594 @@
+use core::{fmt, str::FromStr};
+
+use crate::{Action, Key, Modifiers, WindowEvent};
+
+/// The modifiers that take part in shortcuts. Lock keys are ignored.
+const SHORTCUT_MODIFIERS: Modifiers = Modifiers::CONTROL
+    .union(Modifiers::ALT)
+    .union(Modifiers::SHIFT)
+    .union(Modifiers::SUPER);
+
+/// The names keys are parsed from and labelled with. None contain spaces or
+/// `+`, which separate shortcuts and modifiers.
+const KEY_NAMES: [(Key, &str); 120] = [
+    (Key::Space, "Space"),
+    (Key::Apostrophe, "'"),
+    (Key::Comma, ","),
+    (Key::Minus, "-"),
+    (Key::Period, "."),
+    (Key::Slash, "/"),
+    (Key::Num0, "0"),
+    (Key::Num1, "1"),
+    (Key::Num2, "2"),
+    (Key::Num3, "3"),
+    (Key::Num4, "4"),
+    (Key::Num5, "5"),
+    (Key::Num6, "6"),
+    (Key::Num7, "7"),
+    (Key::Num8, "8"),
+    (Key::Num9, "9"),
+    (Key::Semicolon, ";"),
+    (Key::Equal, "="),
+    (Key::A, "A"),
+    (Key::B, "B"),
+    (Key::C, "C"),
+    (Key::D, "D"),
+    (Key::E, "E"),
+    (Key::F, "F"),
+    (Key::G, "G"),
+    (Key::H, "H"),
+    (Key::I, "I"),
+    (Key::J, "J"),
+    (Key::K, "K"),
+    (Key::L, "L"),
+    (Key::M, "M"),
+    (Key::N, "N"),
+    (Key::O, "O"),
+    (Key::P, "P"),
+    (Key::Q, "Q"),
+    (Key::R, "R"),
+    (Key::S, "S"),
+    (Key::T, "T"),
+    (Key::U, "U"),
+    (Key::V, "V"),
+    (Key::W, "W"),
+    (Key::X, "X"),
+    (Key::Y, "Y"),
+    (Key::Z, "Z"),
+    (Key::LeftBracket, "["),
+    (Key::Backslash, "\\"),
+    (Key::RightBracket, "]"),
+    (Key::GraveAccent, "`"),
+    (Key::World1, "World1"),
+    (Key::World2, "World2"),
+    (Key::Escape, "Escape"),
+    (Key::Enter, "Enter"),
+    (Key::Tab, "Tab"),
+    (Key::Backspace, "Backspace"),
+    (Key::Insert, "Insert"),
+    (Key::Delete, "Delete"),
+    (Key::Right, "Right"),
+    (Key::Left, "Left"),
+    (Key::Down, "Down"),
+    (Key::Up, "Up"),
+    (Key::PageUp, "PageUp"),
+    (Key::PageDown, "PageDown"),
+    (Key::Home, "Home"),
+    (Key::End, "End"),
+    (Key::CapsLock, "CapsLock"),
+    (Key::ScrollLock, "ScrollLock"),
+    (Key::NumLock, "NumLock"),
+    (Key::PrintScreen, "PrintScreen"),
+    (Key::Pause, "Pause"),
+    (Key::F1, "F1"),
+    (Key::F2, "F2"),
+    (Key::F3, "F3"),
+    (Key::F4, "F4"),
+    (Key::F5, "F5"),
+    (Key::F6, "F6"),
+    (Key::F7, "F7"),
+    (Key::F8, "F8"),
+    (Key::F9, "F9"),
+    (Key::F10, "F10"),
+    (Key::F11, "F11"),
+    (Key::F12, "F12"),
+    (Key::F13, "F13"),
+    (Key::F14, "F14"),
+    (Key::F15, "F15"),
+    (Key::F16, "F16"),
+    (Key::F17, "F17"),
+    (Key::F18, "F18"),
+    (Key::F19, "F19"),
+    (Key::F20, "F20"),
+    (Key::F21, "F21"),
+    (Key::F22, "F22"),
+    (Key::F23, "F23"),
+    (Key::F24, "F24"),
+    (Key::F25, "F25"),
+    (Key::Kp0, "Numpad0"),
+    (Key::Kp1, "Numpad1"),
+    (Key::Kp2, "Numpad2"),
+    (Key::Kp3, "Numpad3"),
+    (Key::Kp4, "Numpad4"),
+    (Key::Kp5, "Numpad5"),
+    (Key::Kp6, "Numpad6"),
+    (Key::Kp7, "Numpad7"),
+    (Key::Kp8, "Numpad8"),
+    (Key::Kp9, "Numpad9"),
+    (Key::KpDecimal, "NumpadDecimal"),
+    (Key::KpDivide, "NumpadDivide"),
+    (Key::KpMultiply, "NumpadMultiply"),
+    (Key::KpSubtract, "NumpadSubtract"),
+    (Key::KpAdd, "NumpadAdd"),
+    (Key::KpEnter, "NumpadEnter"),
+    (Key::KpEqual, "NumpadEqual"),
+    (Key::LeftShift, "LeftShift"),
+    (Key::LeftControl, "LeftCtrl"),
+    (Key::LeftAlt, "LeftAlt"),
+    (Key::LeftSuper, "LeftSuper"),
+    (Key::RightShift, "RightShift"),
+    (Key::RightControl, "RightCtrl"),
+    (Key::RightAlt, "RightAlt"),
+    (Key::RightSuper, "RightSuper"),
+    (Key::Menu, "Menu"),
+];
+
+const KEY_ALIASES: [(&str, Key); 8] = [
+    ("esc", Key::Escape),
+    ("return", Key::Enter),
+    ("del", Key::Delete),
+    ("ins", Key::Insert),
+    ("pgup", Key::PageUp),
+    ("pgdn", Key::PageDown),
+    ("plus", Key::Equal),
+    ("backtick", Key::GraveAccent),
+];
+
+fn parse_key(name: &str) -> Option<Key> {
+    KEY_NAMES
+        .iter()
+        .map(|&(key, n)| (n, key))
+        .chain(KEY_ALIASES)
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, key)| key)
+}
+
+fn key_name(key: Key) -> &'static str {
+    KEY_NAMES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or("Unknown", |(_, name)| name)
+}
+
+fn parse_modifier(name: &str) -> Option<Modifiers> {
+    let modifier = match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Modifiers::CONTROL,
+        "shift" => Modifiers::SHIFT,
+        "alt" | "option" | "opt" => Modifiers::ALT,
+        "super" | "cmd" | "command" | "win" | "meta" => Modifiers::SUPER,
+        // Command on macOS and Control elsewhere
+        "cmdorctrl" | "primary" => {
+            if cfg!(target_os = "macos") {
+                Modifiers::SUPER
+            } else {
+                Modifiers::CONTROL
+            }
+        }
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShortcutError {
+    Empty,
+    UnknownKey(String),
+    UnknownModifier(String),
+}
+
+impl fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseShortcutError::Empty => f.write_str("empty shortcut"),
+            ParseShortcutError::UnknownKey(key) => write!(f, "unknown key {:?}", key),
+            ParseShortcutError::UnknownModifier(modifier) => {
+                write!(f, "unknown modifier {:?}", modifier)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseShortcutError {}
+
+/// How shortcut labels are written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LabelStyle {
+    /// `⌃⌥⇧⌘S`
+    MacOs,
+    /// `Ctrl+Alt+Shift+Win+S`
+    Windows,
+    /// `Ctrl+Alt+Shift+Super+S`
+    Linux,
+}
+
+impl LabelStyle {
+    pub fn current() -> LabelStyle {
+        if cfg!(target_os = "macos") {
+            LabelStyle::MacOs
+        } else if cfg!(windows) {
+            LabelStyle::Windows
+        } else {
+            LabelStyle::Linux
+        }
+    }
+}
+
+/// A key with modifiers, such as `Ctrl+Shift+S`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Shortcut {
+    pub key: Key,
+    pub mods: Modifiers,
+}
+
+impl Shortcut {
+    pub fn new(key: Key, mods: Modifiers) -> Shortcut {
+        Shortcut {
+            key,
+            mods: mods & SHORTCUT_MODIFIERS,
+        }
+    }
+
+    /// Whether the event presses this key with exactly these modifiers, not
+    /// counting Caps Lock and Num Lock.
+    pub fn matches(&self, event: &WindowEvent) -> bool {
+        match *event {
+            WindowEvent::Key(key, _, Action::Press, mods) => {
+                self.key == key && self.mods == mods & SHORTCUT_MODIFIERS
+            }
+            _ => false,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        self.label_for(LabelStyle::current())
+    }
+
+    pub fn label_for(&self, style: LabelStyle) -> String {
+        let key = self.key_label(style);
+        let modifiers: &[(Modifiers, &str)] = match style {
+            LabelStyle::MacOs => {
+                let symbols = [
+                    (Modifiers::CONTROL, "⌃"),
+                    (Modifiers::ALT, "⌥"),
+                    (Modifiers::SHIFT, "⇧"),
+                    (Modifiers::SUPER, "⌘"),
+                ];
+                let mut label: String = symbols
+                    .iter()
+                    .filter(|(modifier, _)| self.mods.contains(*modifier))
+                    .map(|(_, symbol)| *symbol)
+                    .collect();
+                label.push_str(&key);
+                return label;
+            }
+            LabelStyle::Windows => &[
+                (Modifiers::CONTROL, "Ctrl"),
+                (Modifiers::ALT, "Alt"),
+                (Modifiers::SHIFT, "Shift"),
+                (Modifiers::SUPER, "Win"),
+            ],
+            LabelStyle::Linux => &[
+                (Modifiers::CONTROL, "Ctrl"),
+                (Modifiers::ALT, "Alt"),
+                (Modifiers::SHIFT, "Shift"),
+                (Modifiers::SUPER, "Super"),
+            ],
+        };
+        let mut label = String::new();
+        for (modifier, name) in modifiers {
+            if self.mods.contains(*modifier) {
+                label.push_str(name);
+                label.push('+');
+            }
+        }
+        label.push_str(&key);
+        label
+    }
+
+    fn key_label(&self, style: LabelStyle) -> String {
+        let mac_symbol = match self.key {
+            Key::Enter => Some("↩"),
+            Key::Backspace => Some("⌫"),
+            Key::Delete => Some("⌦"),
+            Key::Escape => Some("⎋"),
+            Key::Tab => Some("⇥"),
+            Key::Left => Some("←"),
+            Key::Right => Some("→"),
+            Key::Up => Some("↑"),
+            Key::Down => Some("↓"),
+            _ => None,
+        };
+        match mac_symbol {
+            Some(symbol) if LabelStyle::MacOs == style => symbol.into(),
+            _ => key_name(self.key).into(),
+        }
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // A trailing "+" is the key itself, as in "Ctrl++"
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        let key = match key {
+            "+" => Key::Equal,
+            _ => parse_key(key).ok_or_else(|| ParseShortcutError::UnknownKey(key.into()))?,
+        };
+        let mut mods = Modifiers::empty();
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            mods |= parse_modifier(modifier.trim())
+                .ok_or_else(|| ParseShortcutError::UnknownModifier(modifier.into()))?;
+        }
+        Ok(Shortcut::new(key, mods))
+    }
+}
+
+/// The canonical form, which parses back into the same shortcut.
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label_for(LabelStyle::Linux))
+    }
+}
+
+fn is_modifier_key(key: Key) -> bool {
+    matches!(
+        key,
+        Key::LeftShift
+            | Key::LeftControl
+            | Key::LeftAlt
+            | Key::LeftSuper
+            | Key::RightShift
+            | Key::RightControl
+            | Key::RightAlt
+            | Key::RightSuper
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SequenceMatch {
+    /// The event does not continue the sequence.
+    None,
+    /// The event continues the sequence, which awaits more shortcuts.
+    Pending,
+    Complete,
+}
+
+/// A chord of shortcuts pressed one after the other, such as `Ctrl+K Ctrl+C`.
+#[derive(Clone, Debug)]
+pub struct ShortcutSequence {
+    shortcuts: Vec<Shortcut>,
+    /// The longest pause between two shortcuts, in seconds.
+    timeout: f64,
+    progress: usize,
+    last_time: f64,
+}
+
+impl ShortcutSequence {
+    /// Creates a sequence that waits at most one second between shortcuts.
+    pub fn new(shortcuts: Vec<Shortcut>) -> ShortcutSequence {
+        ShortcutSequence {
+            shortcuts,
+            timeout: 1.0,
+            progress: 0,
+            last_time: 0.0,
+        }
+    }
+
+    pub fn shortcuts(&self) -> &[Shortcut] {
+        &self.shortcuts
+    }
+
+    pub fn timeout(&self) -> f64 {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: f64) {
+        self.timeout = timeout;
+    }
+
+    pub fn reset(&mut self) {
+        self.progress = 0;
+    }
+
+    /// Advances the sequence with a timestamped event. Releases, other events
+    /// and presses of modifier keys leave it unchanged.
+    pub fn handle_event(&mut self, (time, event): &(f64, WindowEvent)) -> SequenceMatch {
+        let key = match *event {
+            WindowEvent::Key(key, _, Action::Press, _) => key,
+            _ => return SequenceMatch::None,
+        };
+        if is_modifier_key(key) || self.shortcuts.is_empty() {
+            return SequenceMatch::None;
+        }
+        if self.progress > 0 && time - self.last_time > self.timeout {
+            self.progress = 0;
+        }
+        if !self.shortcuts[self.progress].matches(event) {
+            // The press may start the sequence over
+            self.progress = 0;
+            if !self.shortcuts[0].matches(event) {
+                return SequenceMatch::None;
+            }
+        }
+        self.progress += 1;
+        self.last_time = *time;
+        if self.progress == self.shortcuts.len() {
+            self.progress = 0;
+            SequenceMatch::Complete
+        } else {
+            SequenceMatch::Pending
+        }
+    }
+}
+
+impl FromStr for ShortcutSequence {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let shortcuts = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if shortcuts.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        Ok(ShortcutSequence::new(shortcuts))
+    }
+}
+
+impl fmt::Display for ShortcutSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, shortcut) in self.shortcuts.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            fmt::Display::fmt(shortcut, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn press(key: Key, mods: Modifiers) -> WindowEvent {
+        WindowEvent::Key(key, Scancode(0), Action::Press, mods)
+    }
+
+    #[test]
+    fn parse_shortcut() {
+        let shortcut: Shortcut = "Ctrl+Shift+S".parse().unwrap();
+        assert_eq!(
+            Shortcut::new(Key::S, Modifiers::CONTROL | Modifiers::SHIFT),
+            shortcut
+        );
+        let shortcut: Shortcut = "super + alt + f4".parse().unwrap();
+        assert_eq!(
+            Shortcut::new(Key::F4, Modifiers::SUPER | Modifiers::ALT),
+            shortcut
+        );
+        assert_eq!(
+            Ok(Shortcut::new(Key::Num1, Modifiers::ALT)),
+            "Alt+1".parse()
+        );
+        assert_eq!(
+            Ok(Shortcut::new(Key::Minus, Modifiers::CONTROL)),
+            "Ctrl+-".parse()
+        );
+        assert_eq!(
+            Ok(Shortcut::new(Key::Equal, Modifiers::CONTROL)),
+            "Ctrl++".parse()
+        );
+        assert_eq!(
+            Err(ParseShortcutError::UnknownModifier("Hyper".into())),
+            "Hyper+S".parse::<Shortcut>()
+        );
+        assert_eq!(
+            Err(ParseShortcutError::UnknownKey("Foo".into())),
+            "Ctrl+Foo".parse::<Shortcut>()
+        );
+        assert_eq!(Err(ParseShortcutError::Empty), "Ctrl+".parse::<Shortcut>());
+    }
+
+    #[test]
+    fn match_shortcut() {
+        let shortcut: Shortcut = "Ctrl+S".parse().unwrap();
+        assert!(shortcut.matches(&press(Key::S, Modifiers::CONTROL | Modifiers::NUM_LOCK)));
+        assert!(!shortcut.matches(&press(Key::S, Modifiers::CONTROL | Modifiers::SHIFT)));
+        assert!(!shortcut.matches(&press(Key::S, Modifiers::empty())));
+        let release = WindowEvent::Key(Key::S, Scancode(0), Action::Release, Modifiers::CONTROL);
+        assert!(!shortcut.matches(&release));
+    }
+
+    #[test]
+    fn shortcut_labels() {
+        let shortcut: Shortcut = "Ctrl+Shift+Super+Enter".parse().unwrap();
+        assert_eq!("⌃⇧⌘↩", shortcut.label_for(LabelStyle::MacOs));
+        assert_eq!(
+            "Ctrl+Shift+Win+Enter",
+            shortcut.label_for(LabelStyle::Windows)
+        );
+        assert_eq!("Ctrl+Shift+Super+Enter", shortcut.to_string());
+        let shortcut: Shortcut = "Alt+[".parse().unwrap();
+        assert_eq!("Alt+[", shortcut.to_string());
+        assert_eq!(Ok(shortcut), shortcut.to_string().parse());
+        let shortcut = Shortcut::new(Key::Kp0, Modifiers::CONTROL);
+        assert_eq!("Ctrl+Numpad0", shortcut.to_string());
+    }
+
+    #[test]
+    fn key_names() {
+        for key in Key::all() {
+            let shortcut = Shortcut::new(key, Modifiers::ALT);
+            assert_eq!(Ok(shortcut), shortcut.to_string().parse(), "{:?}", key);
+        }
+        let page_up = Shortcut::new(Key::PageUp, Modifiers::empty());
+        assert_eq!(Ok(page_up), "pageup".parse());
+        assert_eq!(Ok(page_up), "PgUp".parse());
+        assert!("Kp0".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn shortcut_sequence() {
+        let mut sequence: ShortcutSequence = "Ctrl+K Ctrl+C".parse().unwrap();
+        assert_eq!("Ctrl+K Ctrl+C", sequence.to_string());
+
+        let ctrl_k = press(Key::K, Modifiers::CONTROL);
+        let ctrl_c = press(Key::C, Modifiers::CONTROL);
+        let ctrl = press(Key::LeftControl, Modifiers::empty());
+        assert_eq!(
+            SequenceMatch::Pending,
+            sequence.handle_event(&(1.0, ctrl_k.clone()))
+        );
+        assert_eq!(SequenceMatch::None, sequence.handle_event(&(1.2, ctrl)));
+        assert_eq!(
+            SequenceMatch::Complete,
+            sequence.handle_event(&(1.5, ctrl_c.clone()))
+        );
+
+        // Too slow
+        assert_eq!(
+            SequenceMatch::Pending,
+            sequence.handle_event(&(2.0, ctrl_k.clone()))
+        );
+        assert_eq!(
+            SequenceMatch::None,
+            sequence.handle_event(&(3.5, ctrl_c.clone()))
+        );
+
+        // A wrong key cancels, but may start the sequence over
+        assert_eq!(
+            SequenceMatch::Pending,
+            sequence.handle_event(&(4.0, ctrl_k.clone()))
+        );
+        assert_eq!(
+            SequenceMatch::Pending,
+            sequence.handle_event(&(4.1, ctrl_k))
+        );
+        assert_eq!(
+            SequenceMatch::None,
+            sequence.handle_event(&(4.2, press(Key::X, Modifiers::empty())))
+        );
+        assert_eq!(SequenceMatch::None, sequence.handle_event(&(4.3, ctrl_c)));
+    }
+}
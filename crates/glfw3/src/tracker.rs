@@ -0,0 +1,218 @@
+use core::cell::RefCell;
+
+use glfw3_sys as sys;
+
+use crate::{
+    callbacks, gamepad::gamepad_state, Event, GamepadAxis, GamepadButton, GamepadState, Glfw,
+    JoystickId,
+};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GamepadEvent {
+    ButtonPressed(GamepadButton),
+    ButtonReleased(GamepadButton),
+    /// The new position, after the dead-zone has been applied.
+    AxisMoved(GamepadAxis, f32),
+}
+
+const AXIS_COUNT: usize = sys::GLFW_GAMEPAD_AXIS_LAST as usize + 1;
+
+/// Turns the polled state of every connected gamepad into [`Event::Gamepad`]
+/// events. Install it with [`Glfw::set_gamepad_tracker`].
+#[derive(Clone, Debug)]
+pub struct GamepadTracker {
+    dead_zones: [f32; AXIS_COUNT],
+    previous: [Option<GamepadState>; 16],
+}
+
+impl Default for GamepadTracker {
+    fn default() -> Self {
+        GamepadTracker::new()
+    }
+}
+
+fn rest_position(axis: GamepadAxis) -> f32 {
+    match axis {
+        GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => -1.0,
+        _ => 0.0,
+    }
+}
+
+fn rest_state() -> GamepadState {
+    let mut state = GamepadState::default();
+    for axis in GamepadAxis::all() {
+        state.set_axis(axis, rest_position(axis));
+    }
+    state
+}
+
+impl GamepadTracker {
+    /// Creates a tracker with a dead-zone of `0.1` on every axis.
+    pub fn new() -> GamepadTracker {
+        GamepadTracker {
+            dead_zones: [0.1; AXIS_COUNT],
+            previous: [None; 16],
+        }
+    }
+
+    pub fn dead_zone(&self, axis: GamepadAxis) -> f32 {
+        self.dead_zones[axis as usize]
+    }
+
+    /// Positions closer than `dead_zone` to the rest position of the axis,
+    /// `0.0` for sticks and `-1.0` for triggers, are reported as at rest.
+    pub fn set_dead_zone(&mut self, axis: GamepadAxis, dead_zone: f32) {
+        self.dead_zones[axis as usize] = dead_zone.abs();
+    }
+
+    pub fn set_dead_zones(&mut self, dead_zone: f32) {
+        for axis in GamepadAxis::all() {
+            self.set_dead_zone(axis, dead_zone);
+        }
+    }
+
+    fn filter(&self, mut state: GamepadState) -> GamepadState {
+        for axis in GamepadAxis::all() {
+            let rest = rest_position(axis);
+            if (state.axis(axis) - rest).abs() < self.dead_zone(axis) {
+                state.set_axis(axis, rest);
+            }
+        }
+        state
+    }
+
+    /// Records a new sample, `None` if the gamepad is absent, and returns
+    /// what changed since the previous one. An absent gamepad is at rest, so
+    /// a disconnection releases its buttons and recenters its axes.
+    fn update(&mut self, id: JoystickId, state: Option<GamepadState>) -> Vec<GamepadEvent> {
+        let state = state.map(|state| self.filter(state));
+        let previous = core::mem::replace(&mut self.previous[id as usize], state);
+        if previous.is_none() && state.is_none() {
+            return Vec::new();
+        }
+        let previous = previous.unwrap_or_else(rest_state);
+        let state = state.unwrap_or_else(rest_state);
+        let mut events = Vec::new();
+        for button in GamepadButton::all() {
+            match (previous.button(button), state.button(button)) {
+                (false, true) => events.push(GamepadEvent::ButtonPressed(button)),
+                (true, false) => events.push(GamepadEvent::ButtonReleased(button)),
+                _ => {}
+            }
+        }
+        for axis in GamepadAxis::all() {
+            if previous.axis(axis) != state.axis(axis) {
+                events.push(GamepadEvent::AxisMoved(axis, state.axis(axis)));
+            }
+        }
+        events
+    }
+}
+
+thread_local! {
+    static TRACKER: RefCell<Option<GamepadTracker>> = const { RefCell::new(None) };
+}
+
+/// Samples every gamepad and delivers the changes to the current event handler.
+pub(crate) fn track_gamepads() {
+    let events = TRACKER.with(|tracker| {
+        let mut tracker = tracker.borrow_mut();
+        let Some(tracker) = tracker.as_mut() else {
+            return Vec::new();
+        };
+        let time = unsafe { sys::glfwGetTime() };
+        JoystickId::all()
+            .flat_map(|id| {
+                tracker
+                    .update(id, gamepad_state(id))
+                    .into_iter()
+                    .map(move |event| (time, Event::Gamepad(id, event)))
+            })
+            .collect()
+    });
+    // The tracker is released first so that the handler may replace it
+    for event in events {
+        callbacks::call_handler(event);
+    }
+}
+
+pub(crate) fn clear_tracker() {
+    let _ = TRACKER.try_with(|tracker| tracker.borrow_mut().take());
+}
+
+impl Glfw {
+    /// Installs a tracker that samples every gamepad after events are processed
+    /// by [`Glfw::poll_events`], [`Glfw::wait_events`] and
+    /// [`Glfw::wait_events_timeout`], or removes it with `None`.
+    pub fn set_gamepad_tracker(&self, tracker: Option<GamepadTracker>) {
+        TRACKER.with(|ref_cell| *ref_cell.borrow_mut() = tracker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn gamepad_tracker() {
+        let mut tracker = GamepadTracker::new();
+        let id = JoystickId::Joystick1;
+
+        let mut state = GamepadState::default();
+        state.set_axis(GamepadAxis::LeftTrigger, -1.0);
+        state.set_axis(GamepadAxis::RightTrigger, -0.95);
+        state.set_axis(GamepadAxis::LeftX, 0.05);
+        assert_eq!(Vec::<GamepadEvent>::new(), tracker.update(id, Some(state)));
+
+        state.set_button(GamepadButton::A, true);
+        state.set_axis(GamepadAxis::LeftX, 0.5);
+        assert_eq!(
+            vec![
+                GamepadEvent::ButtonPressed(GamepadButton::A),
+                GamepadEvent::AxisMoved(GamepadAxis::LeftX, 0.5),
+            ],
+            tracker.update(id, Some(state))
+        );
+
+        state.set_button(GamepadButton::A, false);
+        state.set_axis(GamepadAxis::LeftX, -0.05);
+        assert_eq!(
+            vec![
+                GamepadEvent::ButtonReleased(GamepadButton::A),
+                GamepadEvent::AxisMoved(GamepadAxis::LeftX, 0.0),
+            ],
+            tracker.update(id, Some(state))
+        );
+
+        // Buttons held while connecting are reported against the rest state
+        assert!(tracker.update(id, None).is_empty());
+        state.set_button(GamepadButton::Start, true);
+        assert_eq!(
+            vec![GamepadEvent::ButtonPressed(GamepadButton::Start)],
+            tracker.update(id, Some(state))
+        );
+    }
+
+    #[test]
+    fn gamepad_disconnected() {
+        let mut tracker = GamepadTracker::new();
+        let id = JoystickId::Joystick2;
+
+        let mut state = GamepadState::default();
+        state.set_button(GamepadButton::B, true);
+        state.set_axis(GamepadAxis::RightY, 0.8);
+        state.set_axis(GamepadAxis::LeftTrigger, 1.0);
+        state.set_axis(GamepadAxis::RightTrigger, -1.0);
+        tracker.update(id, Some(state));
+
+        assert_eq!(
+            vec![
+                GamepadEvent::ButtonReleased(GamepadButton::B),
+                GamepadEvent::AxisMoved(GamepadAxis::RightY, 0.0),
+                GamepadEvent::AxisMoved(GamepadAxis::LeftTrigger, -1.0),
+            ],
+            tracker.update(id, None)
+        );
+        assert!(tracker.update(id, None).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use core::{ffi::CStr, ptr, slice};
+
+use glfw3_sys as sys;
+
+use crate::{Error, Glfw, Window, GLFW_NOT_INITIALIZED};
+
+const VK_SUCCESS: sys::VkResult = 0;
+
+/// The error returned by [`Window::create_surface`].
+///
+/// `error` is `None` when the failure was reported by Vulkan rather than GLFW.
+#[derive(Debug)]
+pub struct CreateSurfaceError {
+    pub result: sys::VkResult,
+    pub error: Option<Error>,
+}
+
+impl Glfw {
+    /// Sets the `vkGetInstanceProcAddr` that GLFW uses instead of loading the
+    /// Vulkan loader itself. It only takes effect during [`Glfw::init`].
+    ///
+    /// # Safety
+    ///
+    /// `loader` must be a valid `vkGetInstanceProcAddr` implementation.
+    #[doc(alias = "glfwInitVulkanLoader")]
+    pub unsafe fn init_vulkan_loader(loader: sys::PFN_vkGetInstanceProcAddr) {
+        unsafe { sys::glfwInitVulkanLoader(loader) }
+    }
+
+    #[doc(alias = "glfwVulkanSupported")]
+    pub fn vulkan_supported(&self) -> bool {
+        unsafe {
+            let supported = sys::glfwVulkanSupported();
+            Glfw::get_error().expect(GLFW_NOT_INITIALIZED);
+            sys::GLFW_TRUE == supported
+        }
+    }
+
+    /// The instance extensions needed to create window surfaces.
+    ///
+    /// Empty if Vulkan is unavailable or window surfaces are not supported.
+    #[doc(alias = "glfwGetRequiredInstanceExtensions")]
+    pub fn required_instance_extensions(&self) -> Vec<&'static CStr> {
+        unsafe {
+            let mut count = 0;
+            let names = sys::glfwGetRequiredInstanceExtensions(&mut count);
+            if let Some(err) = Glfw::get_error().err() {
+                log::debug!("glfwGetRequiredInstanceExtensions failed: {:?}", err);
+            }
+            if names.is_null() {
+                return Vec::new();
+            }
+            // The names are string literals inside GLFW and outlive termination
+            slice::from_raw_parts(names, count as usize)
+                .iter()
+                .map(|&name| CStr::from_ptr(name))
+                .collect()
+        }
+    }
+
+    /// `None` if Vulkan is unavailable or the function is not found.
+    ///
+    /// # Safety
+    ///
+    /// `instance` must be null or a valid Vulkan instance.
+    #[doc(alias = "glfwGetInstanceProcAddress")]
+    pub unsafe fn get_instance_proc_address(
+        &self,
+        instance: sys::VkInstance,
+        procname: &CStr,
+    ) -> sys::GLFWvkproc {
+        unsafe {
+            let proc = sys::glfwGetInstanceProcAddress(instance, procname.as_ptr());
+            if let Some(err) = Glfw::get_error().err() {
+                log::debug!("glfwGetInstanceProcAddress failed: {:?}", err);
+            }
+            proc
+        }
+    }
+
+    /// Fails if Vulkan is unavailable.
+    ///
+    /// # Safety
+    ///
+    /// `device` must be a valid physical device of `instance`.
+    #[doc(alias = "glfwGetPhysicalDevicePresentationSupport")]
+    pub unsafe fn physical_device_presentation_support(
+        &self,
+        instance: sys::VkInstance,
+        device: sys::VkPhysicalDevice,
+        queue_family: u32,
+    ) -> Result<bool, Error> {
+        unsafe {
+            let supported =
+                sys::glfwGetPhysicalDevicePresentationSupport(instance, device, queue_family);
+            Glfw::get_error()?;
+            Ok(sys::GLFW_TRUE == supported)
+        }
+    }
+}
+
+impl Window {
+    /// Creates a surface that the caller must destroy with `vkDestroySurfaceKHR`
+    /// before destroying `instance`.
+    ///
+    /// The window must have been created with [`ClientApi::None`](crate::ClientApi::None).
+    ///
+    /// # Safety
+    ///
+    /// `instance` must be a valid Vulkan instance created with the
+    /// [required extensions](Glfw::required_instance_extensions).
+    #[doc(alias = "glfwCreateWindowSurface")]
+    pub unsafe fn create_surface(
+        &self,
+        instance: sys::VkInstance,
+        allocator: Option<&sys::VkAllocationCallbacks>,
+    ) -> Result<sys::VkSurfaceKHR, CreateSurfaceError> {
+        let allocator = allocator.map(|a| a as *const _).unwrap_or(ptr::null());
+        let mut surface = 0;
+        unsafe {
+            let result =
+                sys::glfwCreateWindowSurface(instance, self.window_ptr, allocator, &mut surface);
+            let error = Glfw::get_error().err();
+            if VK_SUCCESS == result && error.is_none() {
+                Ok(surface)
+            } else {
+                Err(CreateSurfaceError { result, error })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ash")]
+mod ash_interop {
+    use ash::vk::{self, Handle};
+    use glfw3_sys as sys;
+
+    use crate::{Error, Glfw, Window};
+
+    fn instance_ptr(instance: vk::Instance) -> sys::VkInstance {
+        instance.as_raw() as usize as sys::VkInstance
+    }
+
+    impl Glfw {
+        /// [`Glfw::physical_device_presentation_support`] for `ash` handles.
+        ///
+        /// # Safety
+        ///
+        /// `device` must be a valid physical device of `instance`.
+        pub unsafe fn ash_presentation_support(
+            &self,
+            instance: vk::Instance,
+            device: vk::PhysicalDevice,
+            queue_family: u32,
+        ) -> Result<bool, Error> {
+            let device = device.as_raw() as usize as sys::VkPhysicalDevice;
+            unsafe {
+                self.physical_device_presentation_support(
+                    instance_ptr(instance),
+                    device,
+                    queue_family,
+                )
+            }
+        }
+    }
+
+    impl Window {
+        /// [`Window::create_surface`] for `ash` handles.
+        ///
+        /// # Safety
+        ///
+        /// See [`Window::create_surface`].
+        pub unsafe fn create_ash_surface(
+            &self,
+            instance: vk::Instance,
+            allocator: Option<&vk::AllocationCallbacks<'_>>,
+        ) -> Result<vk::SurfaceKHR, vk::Result> {
+            let allocator = allocator.map(|a| unsafe {
+                &*(a as *const vk::AllocationCallbacks as *const sys::VkAllocationCallbacks)
+            });
+            unsafe {
+                self.create_surface(instance_ptr(instance), allocator)
+                    .map(vk::SurfaceKHR::from_raw)
+                    .map_err(|err| {
+                        if let Some(error) = err.error {
+                            log::warn!("glfwCreateWindowSurface failed: {:?}", error);
+                        }
+                        vk::Result::from_raw(err.result)
+                    })
+            }
+        }
+    }
+}
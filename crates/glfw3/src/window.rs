@@ -245,6 +245,8 @@ impl Drop for Window {
     fn drop(&mut self) {
         unsafe {
             if self.window_ptr != ptr::null_mut() {
+                crate::cursor::stop_animation(self.window_ptr);
+                crate::callbacks::reset_mouse_motion(self.window_ptr);
                 sys::glfwDestroyWindow(self.window_ptr);
                 if let Some(err) = Glfw::get_error().err() {
                     log::warn!("glfwDestroyWindow failed: {:?}", err);